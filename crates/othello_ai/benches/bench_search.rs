@@ -0,0 +1,69 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use othello_ai::{AI, AlphaBetaAI, DeepeningAI, MtdfAI, PvsAI};
+use othello_ai::ordering::MoveOrdering;
+use othello_game::{DefaultGame, Game};
+
+/**
+ * Positions from the middle of random games, the same every time.
+ */
+fn random_positions(num_positions: usize) -> Vec<DefaultGame> {
+    let mut rng = StdRng::seed_from_u64(0);
+    (0..num_positions).map(|_| {
+        let mut game = DefaultGame::new();
+        for _ in 0..20 {
+            game = match game.valid_moves(game.next_turn).choose(&mut rng) {
+                Some(mov) => game.apply(*mov),
+                None => game.pass(),
+            };
+        }
+        game
+    }).collect()
+}
+
+/**
+ * Time an AI choosing a move in each position, with a fresh AI for each search so that they don't
+ * find each other's results in the transposition table.  The time depends on the machine, but the
+ * number of nodes doesn't, so that is printed too.
+ */
+fn bench_search<A: AI>(c: &mut Criterion, name: &str, games: &[DefaultGame], new_ai: impl Fn() -> A) {
+    let nodes: usize = games.iter()
+        .map(|game| {
+            let ai = new_ai();
+            ai.choose_move(game);
+            ai.info().unwrap().last_nodes_searched.get()
+        })
+        .sum();
+    println!("{name}: {nodes} nodes for {} positions", games.len());
+
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            for game in games {
+                let mov = new_ai().choose_move(game);
+                black_box(mov);
+            }
+        });
+    });
+}
+
+pub fn search_benchmark(c: &mut Criterion) {
+    const NUM_POSITIONS: usize = 20;
+    const TABLE_SIZE: usize = 1 << 12;
+    const DEPTH: usize = 6;
+
+    let games = random_positions(NUM_POSITIONS);
+
+    for (name, move_ordering) in [("alphabeta_generated", MoveOrdering::Generated), ("alphabeta_heuristic", MoveOrdering::Heuristic)] {
+        bench_search(c, name, &games, || AlphaBetaAI::new(DEPTH).with_move_ordering(move_ordering).with_table_size(TABLE_SIZE));
+    }
+    bench_search(c, "pvs", &games, || PvsAI::new(DEPTH).with_table_size(TABLE_SIZE));
+    bench_search(c, "mtdf", &games, || MtdfAI::new(DEPTH).with_table_size(TABLE_SIZE));
+}
+
+criterion_group!(benches, search_benchmark);
+criterion_main!(benches);
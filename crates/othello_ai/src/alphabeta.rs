@@ -1,37 +1,127 @@
-use crate::{AI, evaluate_immediate, pick_best_move, Score};
-use othello_game::{convert, Board, Colour, Game, GameRepr, Move};
+use crate::{AI, AIInfo, SearchResult, INFINITY};
+use crate::deepening::{Deepening, DeepeningAI, Node, Search};
+use crate::ordering::MoveOrdering;
+use crate::transposition::{Bound, Entry};
+use othello_game::{convert, Board, Game, GameRepr, Move, Score};
 use othello_game::bitboardgame::BitBoardBoard;
 
-#[derive(Clone)]
+/**
+ * A negamax search with alpha-beta pruning, deepened one ply at a time until it reaches its
+ * maximum depth or runs out of time or nodes.  When a limit is hit part way through a depth, the best
+ * move from the last complete depth is used.
+ *
+ * Positions are remembered in a transposition table, which is kept from one search to the next,
+ * and moves are searched in the order given by `move_ordering`.
+ */
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AlphaBetaAI {
-    pub max_depth: usize,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub deepening: Deepening,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "MoveOrdering::is_heuristic"))]
+    pub move_ordering: MoveOrdering,
+}
+
+impl AlphaBetaAI {
+    /**
+     * An AI that searches to the given depth, with no time or node limit.
+     */
+    pub fn new(max_depth: usize) -> Self {
+        AlphaBetaAI { deepening: Deepening::new(max_depth), move_ordering: MoveOrdering::default() }
+    }
+
+    pub fn with_move_ordering(self, move_ordering: MoveOrdering) -> Self {
+        AlphaBetaAI { move_ordering, ..self }
+    }
+
+    /**
+     * Search for the best move for the player to move.  The score is from their point of view.
+     */
+    pub fn search<B: Board>(&self, game: &GameRepr<B>) -> SearchResult {
+        self.deepening.search(game, |search, moves, depth, _| {
+            let (best_move, score) = self.search_root(game, moves, depth, search)?;
+            Some(SearchResult { best_move, score, depth, pv: best_move.into_iter().collect() })
+        })
+    }
+
+    /**
+     * Search each move to the given depth, returning None if the search ran out of budget.
+     */
+    fn search_root<B: Board>(&self, game: &GameRepr<B>, moves: &[Move], depth: usize, search: &mut Search) -> Option<(Option<Move>, Score)> {
+        if moves.is_empty() {
+            let score = -self.negamax(&game.pass(), 1, depth, -INFINITY, INFINITY, search)?;
+            return Some((None, score));
+        }
+
+        let mut best = (None, -INFINITY);
+        for &mov in moves {
+            let score = -self.negamax(&game.apply(mov), 1, depth - 1, -INFINITY, -best.1, search)?;
+            if best.0.is_none() || score > best.1 {
+                best = (Some(mov), score);
+            }
+        }
+        Some(best)
+    }
+
+    /**
+     * The score of a position `ply` moves from the root, for the player to move, or None if the search
+     * ran out of budget.
+     */
+    fn negamax<B: Board>(&self, game: &GameRepr<B>, ply: usize, depth: usize, alpha: Score, beta: Score, search: &mut Search) -> Option<Score> {
+        if search.spend() {
+            return None;
+        }
+
+        let mut moves = match Node::new(game, depth) {
+            Node::Leaf(score) => return Some(score),
+            Node::Pass(game) => return self.negamax(&game, ply + 1, depth, -beta, -alpha, search).map(|score| -score),
+            Node::Moves(moves) => moves,
+        };
+
+        let key = game.zobrist_key();
+        let table_depth = depth.min(u8::MAX as usize) as u8;
+        let entry = search.probe(key);
+        if let Some(entry) = entry.filter(|entry| entry.cuts_off(table_depth, alpha, beta)) {
+            search.info.add_cutoff();
+            return Some(entry.score);
+        }
+        if self.move_ordering.is_heuristic() {
+            search.orderer.order(game, &mut moves, ply, depth, entry.and_then(|entry| entry.best_move));
+        }
+
+        let mut best = (moves[0], -INFINITY);
+        let mut alpha_now = alpha;
+        for mov in moves {
+            let score = -self.negamax(&game.apply(mov), ply + 1, depth - 1, -beta, -alpha_now, search)?;
+            if score > best.1 {
+                best = (mov, score);
+            }
+            if score >= beta {
+                search.orderer.add_cutoff(mov, ply, depth);
+                break;
+            }
+            alpha_now = alpha_now.max(score);
+        }
+
+        let bound = if best.1 <= alpha { Bound::Upper } else if best.1 >= beta { Bound::Lower } else { Bound::Exact };
+        search.table.store(Entry::new(key, table_depth, bound, best.1, Some(best.0)));
+        Some(best.1)
+    }
+}
+
+impl DeepeningAI for AlphaBetaAI {
+    fn deepening_mut(&mut self) -> &mut Deepening {
+        &mut self.deepening
+    }
 }
 
 impl AI for AlphaBetaAI {
     fn choose_move(&self, game: &dyn Game) -> Option<Move> {
         let game: GameRepr<BitBoardBoard> = convert(game);
-        pick_best_move(&game, |g, m| evaluate_to_depth(
-            &g.apply(m),
-            game.next_turn(),
-            -1_000_000,
-            1_000_000,
-            self.max_depth))
+        self.search(&game).best_move
     }
-}
-
-fn evaluate_to_depth<B: Board>(game: &GameRepr<B>, player: Colour, mut alpha: Score, beta: Score, depth: usize) -> Score {
-    if depth == 0 {
-        evaluate_immediate(game, player)
-    } else {
-        /* Evaluate this position as if the opponent will make its best available move. */
-        let opponent = player.opponent();
-        for mov in game.valid_moves(player) {
-            let g = game.apply(mov);
-            let score = -evaluate_to_depth(&g, opponent, -beta, -alpha, depth - 1);
-            if score >= beta { return beta }
-            if score > alpha { alpha = score }
-        }
 
-        alpha
+    fn info(&self) -> Option<AIInfo> {
+        Some(self.deepening.info())
     }
 }
@@ -0,0 +1,283 @@
+/*!
+ * Iterative deepening, shared by the searches that can be given a time or node limit.
+ *
+ * The root is searched to depth 1, then 2, and so on, with the best move from each depth searched
+ * first at the next.  If the search runs out of time or nodes part way through a depth, that depth
+ * is thrown away and the result of the last complete depth is used.
+ *
+ * The AIs that deepen this way each hold a `Deepening`, with their limits and what they keep from
+ * one search to the next, and differ only in how they search each depth.
+ */
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::{AIInfo, evaluate_immediate, SearchResult};
+use crate::ordering::MoveOrderer;
+use crate::transposition::{Entry, TranspositionTable};
+use othello_game::{Board, Game, GameRepr, Move, Score};
+
+/**
+ * The limits of an iteratively deepened search, and the transposition table, move ordering history
+ * and statistics that are kept between searches.
+ */
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Deepening {
+    /**
+     * The number of moves to look ahead, counting the AI's own move.  This is one more than
+     * `MinimaxAI::max_depth` for a search of the same depth.
+     */
+    pub max_depth: usize,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub max_time: Option<Duration>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub max_nodes: Option<usize>,
+    /**
+     * The number of transposition table entries, or None for `TranspositionTable::DEFAULT_SIZE`.
+     */
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub table_size: Option<usize>,
+    /* These are locked by each search, so that the AI can be shared between threads */
+    #[cfg_attr(feature = "serde", serde(skip))]
+    table: Mutex<TranspositionTable>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    orderer: Mutex<MoveOrderer>,
+    /* Statistics from past searches aren't part of the configuration.  Each search counts into its
+     * own and stores them here when it finishes. */
+    #[cfg_attr(feature = "serde", serde(skip))]
+    info: Mutex<AIInfo>,
+}
+
+impl Deepening {
+    /**
+     * Search to the given depth, with no time or node limit.
+     */
+    pub fn new(max_depth: usize) -> Self {
+        Deepening {
+            max_depth,
+            max_time: None,
+            max_nodes: None,
+            table_size: None,
+            table: Mutex::default(),
+            orderer: Mutex::default(),
+            info: Mutex::default(),
+        }
+    }
+
+    /**
+     * The statistics of the searches finished so far.
+     */
+    pub fn info(&self) -> AIInfo {
+        lock(&self.info).clone()
+    }
+
+    /**
+     * Search deeper and deeper, up to `max_depth`, until the budget runs out or every line reaches
+     * the end of the game.  `search_depth` is given the state of the search, the root moves in the
+     * order to search them, the depth, and the result of the previous depth, and returns None if it
+     * ran out of budget.
+     */
+    pub(crate) fn search<B: Board, F>(&self, game: &GameRepr<B>, mut search_depth: F) -> SearchResult
+    where F: FnMut(&mut Search<'_>, &[Move], usize, &SearchResult) -> Option<SearchResult> {
+        let mut moves = game.valid_moves(game.next_turn);
+        let mut table = lock(&self.table);
+        table.resize(self.table_size.unwrap_or(TranspositionTable::DEFAULT_SIZE));
+        table.new_search();
+        let mut orderer = lock(&self.orderer);
+        orderer.new_search();
+        let mut search = Search {
+            budget: Budget::new(self.max_time, self.max_nodes),
+            table: &mut table,
+            orderer: &mut orderer,
+            info: AIInfo::default(),
+        };
+        search.info.begin_search(moves.len());
+
+        let mut result = SearchResult {
+            best_move: moves.first().copied(),
+            score: evaluate_immediate(game, game.next_turn),
+            depth: 0,
+            pv: moves.first().copied().into_iter().collect(),
+        };
+        let empties = (B::SIZE as usize).pow(2) - {
+            let (black, white) = game.scores();
+            (black + white) as usize
+        };
+
+        if !game.is_game_over() {
+            for depth in 1..=self.max_depth {
+                let Some(depth_result) = search_depth(&mut search, &moves, depth, &result) else { break };
+                result = depth_result;
+                search.info.last_depth.set(depth);
+
+                /* Search the best move first at the next depth */
+                if let Some(index) = moves.iter().position(|mov| Some(*mov) == result.best_move) {
+                    moves[..=index].rotate_right(1);
+                }
+                search.budget.enforced = true;
+
+                /* Every line has reached the end of the game */
+                if depth >= empties {
+                    break;
+                }
+            }
+        }
+
+        let mut info = lock(&self.info);
+        search.info.total_nodes_searched.set(info.total_nodes_searched.get());
+        search.info.finish_search();
+        *info = search.info;
+        result
+    }
+}
+
+/**
+ * Lock what a search keeps between searches.  A search that panicked can only have left true results
+ * and useful hints behind, so they are used anyway.
+ */
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl Clone for Deepening {
+    fn clone(&self) -> Self {
+        Deepening {
+            max_depth: self.max_depth,
+            max_time: self.max_time,
+            max_nodes: self.max_nodes,
+            table_size: self.table_size,
+            table: Mutex::new(lock(&self.table).clone()),
+            orderer: Mutex::new(lock(&self.orderer).clone()),
+            info: Mutex::new(self.info()),
+        }
+    }
+}
+
+/**
+ * An AI that deepens its search iteratively, with builders for its limits.
+ */
+pub trait DeepeningAI: Sized {
+    fn deepening_mut(&mut self) -> &mut Deepening;
+
+    /**
+     * Stop searching after about this long.  The first depth is always searched in full.
+     */
+    fn with_time_limit(mut self, max_time: Duration) -> Self {
+        self.deepening_mut().max_time = Some(max_time);
+        self
+    }
+
+    /**
+     * Stop searching after this many nodes.  The first depth is always searched in full.
+     */
+    fn with_node_limit(mut self, max_nodes: usize) -> Self {
+        self.deepening_mut().max_nodes = Some(max_nodes);
+        self
+    }
+
+    /**
+     * Use a transposition table with this many entries, rounded up to a power of two.
+     */
+    fn with_table_size(mut self, table_size: usize) -> Self {
+        self.deepening_mut().table_size = Some(table_size);
+        self
+    }
+}
+
+/**
+ * The state of one search, passed down through the positions it searches.
+ */
+pub(crate) struct Search<'a> {
+    budget: Budget,
+    pub table: &'a mut TranspositionTable,
+    pub orderer: &'a mut MoveOrderer,
+    pub info: AIInfo,
+}
+
+impl Search<'_> {
+    /**
+     * Count a node, returning true if the search should stop.
+     */
+    pub fn spend(&mut self) -> bool {
+        self.info.add_node();
+        self.budget.spend()
+    }
+
+    /**
+     * Look a position up in the transposition table, counting the probe.
+     */
+    pub fn probe(&self, key: u64) -> Option<Entry> {
+        let entry = self.table.probe(key);
+        self.info.add_probe(entry.is_some());
+        entry
+    }
+}
+
+/**
+ * What a search does with a position.
+ */
+pub(crate) enum Node<B: Board> {
+    /**
+     * The game is over or the search has reached its depth, so the position is only evaluated.
+     */
+    Leaf(Score),
+    /**
+     * The player to move has to pass.  A pass doesn't use up any depth, and the opponent is sure to
+     * have a move.
+     */
+    Pass(GameRepr<B>),
+    Moves(Vec<Move>),
+}
+
+impl<B: Board> Node<B> {
+    pub fn new(game: &GameRepr<B>, depth: usize) -> Self {
+        if depth == 0 {
+            return Node::Leaf(evaluate_immediate(game, game.next_turn));
+        }
+        let moves = game.valid_moves(game.next_turn);
+        if !moves.is_empty() {
+            Node::Moves(moves)
+        } else if game.valid_moves(game.next_turn.opponent()).is_empty() {
+            Node::Leaf(evaluate_immediate(game, game.next_turn))
+        } else {
+            Node::Pass(game.pass())
+        }
+    }
+}
+
+/**
+ * The time and nodes a search may use.
+ */
+struct Budget {
+    deadline: Option<Instant>,
+    max_nodes: Option<usize>,
+    nodes: usize,
+    /**
+     * Whether running out stops the search.  It's turned on once the first depth is complete, so
+     * there is always a move to play.
+     */
+    pub enforced: bool,
+}
+
+impl Budget {
+    /* Checking the clock is slow, so only do it every so many nodes */
+    const NODES_PER_CLOCK_CHECK: usize = 1024;
+
+    pub fn new(max_time: Option<Duration>, max_nodes: Option<usize>) -> Self {
+        Budget { deadline: max_time.map(|time| Instant::now() + time), max_nodes, nodes: 0, enforced: false }
+    }
+
+    /**
+     * Count a node, returning true if the search should stop.
+     */
+    pub fn spend(&mut self) -> bool {
+        self.nodes += 1;
+        if !self.enforced {
+            return false;
+        }
+        self.max_nodes.is_some_and(|max_nodes| self.nodes > max_nodes)
+            || (self.nodes.is_multiple_of(Self::NODES_PER_CLOCK_CHECK)
+                && self.deadline.is_some_and(|deadline| Instant::now() >= deadline))
+    }
+}
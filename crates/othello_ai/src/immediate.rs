@@ -2,7 +2,8 @@ use crate::{AI, evaluate_immediate, pick_best_move, Score};
 use othello_game::{convert, Board, Game, GameRepr, Move};
 use othello_game::bitboardgame::BitBoardBoard;
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImmediateAI {
 }
 
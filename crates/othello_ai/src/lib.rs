@@ -1,16 +1,29 @@
 mod alphabeta;
+mod deepening;
 mod immediate;
 pub mod minimax;
+mod mtdf;
+pub mod ordering;
+mod pvs;
 mod random;
+pub mod transposition;
 
 use std::cell::Cell;
 use othello_game::{Board, Colour, Game, GameRepr, Move, Score};
 
 pub use alphabeta::AlphaBetaAI;
+pub use deepening::{Deepening, DeepeningAI};
 pub use immediate::ImmediateAI;
 pub use minimax::MinimaxAI;
+pub use mtdf::MtdfAI;
+pub use pvs::PvsAI;
 pub use random::RandomAI;
 
+/**
+ * A score better than any position can have, for the bounds of a search.
+ */
+pub const INFINITY: Score = 1_000_000;
+
 /**
  * Evaluate this immediate othello_game position, returning a `Score`.  A higher score is considered
  * better.  Evaluation is done from the point of view of the given player, using the "negamax" approach.
@@ -35,16 +48,43 @@ where F: Fn(&GameRepr<B>, Move) -> Score {
     game.valid_moves(game.next_turn()).into_iter().max_by_key(|m| evaluate_move(game, *m))
 }
 
+/**
+ * The outcome of a search: the move to play (None to pass), its score for the player to move, and
+ * the depth that was completed.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchResult {
+    pub best_move: Option<Move>,
+    pub score: Score,
+    pub depth: usize,
+    /**
+     * The moves the search expects to be played, starting with the best move.  Searches that don't
+     * keep track of the line give just the best move.
+     */
+    pub pv: Vec<Move>,
+}
+
 pub trait AI: Clone + Send {
     fn choose_move(&self, game: &dyn Game) -> Option<Move>;
     fn info(&self) -> Option<AIInfo> { None }
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct AIInfo {
     pub total_nodes_searched: Cell<usize>,
     pub last_nodes_searched: Cell<usize>,
     pub last_num_choices: Cell<usize>,
+    /**
+     * The depth reached by the last search, for AIs that deepen until they run out of time.
+     */
+    pub last_depth: Cell<usize>,
+    /**
+     * Transposition table lookups in the last search, how many found their position, and how many
+     * of those ended the search of the position without searching its moves.
+     */
+    pub table_probes: Cell<usize>,
+    pub table_hits: Cell<usize>,
+    pub table_cutoffs: Cell<usize>,
 }
 
 unsafe impl Send for AIInfo {}
@@ -58,6 +98,19 @@ impl AIInfo {
     fn begin_search(&self, num_choices: usize) {
         self.last_num_choices.set(num_choices);
         self.last_nodes_searched.set(0);
+        self.last_depth.set(0);
+        self.table_probes.set(0);
+        self.table_hits.set(0);
+        self.table_cutoffs.set(0);
+    }
+
+    fn add_probe(&self, hit: bool) {
+        self.table_probes.update(|x| x + 1);
+        if hit { self.table_hits.update(|x| x + 1) }
+    }
+
+    fn add_cutoff(&self) {
+        self.table_cutoffs.update(|x| x + 1);
     }
 
     fn finish_search(&self) {
@@ -3,9 +3,12 @@ use crate::{AI, AIInfo, evaluate_immediate, pick_best_move, Score};
 use othello_game::{Board, Colour, convert, Game, GameRepr, Move};
 use othello_game::bitboardgame::BitBoardBoard;
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MinimaxAI {
     pub max_depth: usize,
+    /* Statistics from past searches aren't part of the configuration */
+    #[cfg_attr(feature = "serde", serde(skip))]
     info: AIInfo,
 }
 
@@ -0,0 +1,142 @@
+use crate::{AI, AIInfo, SearchResult, INFINITY};
+use crate::deepening::{Deepening, DeepeningAI, Node, Search};
+use crate::transposition::{Bound, Entry};
+use othello_game::{convert, Board, Game, GameRepr, Move, Score};
+use othello_game::bitboardgame::BitBoardBoard;
+
+/**
+ * MTD(f), as described at https://www.chessprogramming.org/MTD(f).  Each depth is searched with a
+ * series of zero window alpha-beta searches, each of which only finds whether the score is above or
+ * below a guess.  The bounds close in on the score, and the transposition table saves most of the
+ * work of each search from being repeated in the next.
+ *
+ * The first guess at each depth is the score from the depth before, which is usually close, so few
+ * searches are needed.
+ */
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MtdfAI {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub deepening: Deepening,
+}
+
+impl MtdfAI {
+    /**
+     * An AI that searches to the given depth, with no time or node limit.
+     */
+    pub fn new(max_depth: usize) -> Self {
+        MtdfAI { deepening: Deepening::new(max_depth) }
+    }
+
+    /**
+     * Search for the best move for the player to move.  The score is from their point of view.
+     */
+    pub fn search<B: Board>(&self, game: &GameRepr<B>) -> SearchResult {
+        self.deepening.search(game, |search, moves, depth, previous| {
+            let (best_move, score) = self.mtdf(game, moves, depth, previous.score, search)?;
+            Some(SearchResult { best_move, score, depth, pv: best_move.into_iter().collect() })
+        })
+    }
+
+    /**
+     * Close in on the score of the root at the given depth, starting from a guess.  Returns None if
+     * the search ran out of budget.
+     */
+    fn mtdf<B: Board>(&self, game: &GameRepr<B>, moves: &[Move], depth: usize, guess: Score, search: &mut Search) -> Option<(Option<Move>, Score)> {
+        let (mut lower, mut upper) = (-INFINITY, INFINITY);
+        let (mut best_move, mut score) = (moves.first().copied(), guess);
+        while lower < upper {
+            let beta = if score == lower { score + 1 } else { score };
+            let (mov, test_score) = self.test_root(game, moves, depth, beta, search)?;
+            score = test_score;
+            if score < beta {
+                upper = score;
+            } else {
+                /* Only a search that fails high shows which move is best */
+                lower = score;
+                best_move = mov;
+            }
+        }
+        Some((best_move, score))
+    }
+
+    /**
+     * Find whether the root's score is at least `beta`, returning the move that shows it is, or the
+     * best move found if it isn't.
+     */
+    fn test_root<B: Board>(&self, game: &GameRepr<B>, moves: &[Move], depth: usize, beta: Score, search: &mut Search) -> Option<(Option<Move>, Score)> {
+        if moves.is_empty() {
+            let score = -self.test(&game.pass(), 1, depth, 1 - beta, search)?;
+            return Some((None, score));
+        }
+
+        let mut best = (None, -INFINITY);
+        for &mov in moves {
+            let score = -self.test(&game.apply(mov), 1, depth - 1, 1 - beta, search)?;
+            if best.0.is_none() || score > best.1 {
+                best = (Some(mov), score);
+            }
+            if score >= beta { break }
+        }
+        Some(best)
+    }
+
+    /**
+     * A zero window search of a position `ply` moves from the root, finding whether its score for
+     * the player to move is at least `beta`.  The score returned is a bound on the true score: at
+     * least `beta` if it is, and at most `beta - 1` if it isn't.  None if the search ran out of budget.
+     */
+    fn test<B: Board>(&self, game: &GameRepr<B>, ply: usize, depth: usize, beta: Score, search: &mut Search) -> Option<Score> {
+        if search.spend() {
+            return None;
+        }
+
+        let mut moves = match Node::new(game, depth) {
+            Node::Leaf(score) => return Some(score),
+            Node::Pass(game) => return self.test(&game, ply + 1, depth, 1 - beta, search).map(|score| -score),
+            Node::Moves(moves) => moves,
+        };
+
+        let key = game.zobrist_key();
+        let table_depth = depth.min(u8::MAX as usize) as u8;
+        let entry = search.probe(key);
+        if let Some(entry) = entry.filter(|entry| entry.cuts_off(table_depth, beta - 1, beta)) {
+            search.info.add_cutoff();
+            return Some(entry.score);
+        }
+        search.orderer.order(game, &mut moves, ply, depth, entry.and_then(|entry| entry.best_move));
+
+        let mut best = (moves[0], -INFINITY);
+        for mov in moves {
+            let score = -self.test(&game.apply(mov), ply + 1, depth - 1, 1 - beta, search)?;
+            if score > best.1 {
+                best = (mov, score);
+            }
+            if score >= beta {
+                search.orderer.add_cutoff(mov, ply, depth);
+                break;
+            }
+        }
+
+        let bound = if best.1 >= beta { Bound::Lower } else { Bound::Upper };
+        search.table.store(Entry::new(key, table_depth, bound, best.1, Some(best.0)));
+        Some(best.1)
+    }
+}
+
+impl DeepeningAI for MtdfAI {
+    fn deepening_mut(&mut self) -> &mut Deepening {
+        &mut self.deepening
+    }
+}
+
+impl AI for MtdfAI {
+    fn choose_move(&self, game: &dyn Game) -> Option<Move> {
+        let game: GameRepr<BitBoardBoard> = convert(game);
+        self.search(&game).best_move
+    }
+
+    fn info(&self) -> Option<AIInfo> {
+        Some(self.deepening.info())
+    }
+}
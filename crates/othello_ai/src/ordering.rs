@@ -0,0 +1,161 @@
+/*!
+ * Choosing the order to search moves in.  Alpha-beta search prunes the most when the best move is
+ * searched first, so moves that are likely to be good are tried before the others:
+ *
+ * 1. The best move from the transposition table.
+ * 2. Killer moves: moves that caused a cutoff in another position at the same ply.
+ * 3. Moves that have caused cutoffs anywhere, scored by the history heuristic.
+ *
+ * Near the leaves there haven't been enough cutoffs for the history to mean much, so the remaining
+ * moves are ordered by how few moves they leave the opponent, and how good their squares are.  One
+ * ply from the leaves, only the transposition table move is put first.
+ */
+
+use std::cmp::Reverse;
+use std::fmt::{Debug, Formatter};
+
+use othello_game::{Board, Colour, Game, GameRepr, Move, Pos, Score, MAX_BOARD_SIZE};
+
+/**
+ * How a search orders its moves.
+ */
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MoveOrdering {
+    /**
+     * The order the moves are generated in.
+     */
+    Generated,
+    /**
+     * The best move from the transposition table, then killers, then history, as described above.
+     */
+    #[default]
+    Heuristic,
+}
+
+impl MoveOrdering {
+    pub fn is_heuristic(&self) -> bool {
+        *self == MoveOrdering::Heuristic
+    }
+}
+
+/**
+ * The remaining depths at or below which moves are ordered by square value, and by square value and
+ * mobility, rather than by history.  Counting the opponent's replies is only worth it where there
+ * is a lot of search below the move.
+ */
+const SQUARE_ORDERING_DEPTH: usize = 2;
+const MOBILITY_ORDERING_DEPTH: usize = 3;
+
+const NUM_SQUARES: usize = (MAX_BOARD_SIZE as usize) * (MAX_BOARD_SIZE as usize);
+
+/**
+ * How good a square is to play on, whatever the position: corners are best, and the squares next
+ * to them worst, since they give the corner away.
+ */
+pub fn square_value(row: Pos, col: Pos, size: Pos) -> Score {
+    let edge = |i: Pos| i == 0 || i == size - 1;
+    let next_to_edge = |i: Pos| i == 1 || i == size - 2;
+    match (edge(row), edge(col)) {
+        (true, true) => 100,
+        (true, false) if next_to_edge(col) => -20,
+        (false, true) if next_to_edge(row) => -20,
+        (true, false) | (false, true) => 10,
+        (false, false) if next_to_edge(row) && next_to_edge(col) => -50,
+        (false, false) => 0,
+    }
+}
+
+fn colour_index(colour: Colour) -> usize {
+    match colour {
+        Colour::Black => 0,
+        Colour::White => 1,
+    }
+}
+
+fn square_index(mov: Move) -> usize {
+    mov.row as usize * MAX_BOARD_SIZE as usize + mov.col as usize
+}
+
+/**
+ * What a search has learned about which moves cause cutoffs.
+ */
+#[derive(Clone)]
+pub(crate) struct MoveOrderer {
+    /* Two killer moves for each ply, the most recent first */
+    killers: Vec<[Option<Move>; 2]>,
+    /* The history score of each square for each player */
+    history: Box<[[u32; NUM_SQUARES]; 2]>,
+}
+
+impl Default for MoveOrderer {
+    fn default() -> Self {
+        MoveOrderer { killers: Vec::new(), history: Box::new([[0; NUM_SQUARES]; 2]) }
+    }
+}
+
+impl MoveOrderer {
+    /**
+     * Start a new search.  Killers are forgotten, and older history counts for less.
+     */
+    pub fn new_search(&mut self) {
+        self.killers.clear();
+        for score in self.history.as_flattened_mut() {
+            *score /= 2;
+        }
+    }
+
+    /**
+     * Put the moves in the order to search them, at the given ply from the root and remaining depth.
+     */
+    pub fn order<B: Board>(&self, game: &GameRepr<B>, moves: &mut [Move], ply: usize, depth: usize, hash_move: Option<Move>) {
+        /* The replies to these moves are only evaluated, which is quicker than sorting them */
+        if depth <= 1 {
+            if let Some(index) = moves.iter().position(|mov| Some(*mov) == hash_move) {
+                moves[..=index].rotate_right(1);
+            }
+            return;
+        }
+
+        let killers = self.killers.get(ply).copied().unwrap_or_default();
+        let history = &self.history[colour_index(game.next_turn)];
+
+        moves.sort_by_cached_key(|&mov| {
+            let key = if Some(mov) == hash_move {
+                (3, 0)
+            } else if let Some(i) = killers.iter().position(|killer| *killer == Some(mov)) {
+                (2, -(i as i64))
+            } else if depth <= SQUARE_ORDERING_DEPTH {
+                (1, square_value(mov.row, mov.col, B::SIZE) as i64)
+            } else if depth <= MOBILITY_ORDERING_DEPTH {
+                let mobility = game.apply(mov).valid_moves(game.next_turn.opponent()).len() as i64;
+                (1, square_value(mov.row, mov.col, B::SIZE) as i64 - 10 * mobility)
+            } else {
+                (1, history[square_index(mov)] as i64)
+            };
+            Reverse(key)
+        });
+    }
+
+    /**
+     * Remember a move that caused a cutoff at the given ply and remaining depth.
+     */
+    pub fn add_cutoff(&mut self, mov: Move, ply: usize, depth: usize) {
+        let killers = &mut self.killers;
+        if killers.len() <= ply {
+            killers.resize(ply + 1, [None; 2]);
+        }
+        if killers[ply][0] != Some(mov) {
+            killers[ply] = [Some(mov), killers[ply][0]];
+        }
+
+        let score = &mut self.history[colour_index(mov.player)][square_index(mov)];
+        *score = score.saturating_add((depth * depth) as u32);
+    }
+}
+
+impl Debug for MoveOrderer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MoveOrderer").finish_non_exhaustive()
+    }
+}
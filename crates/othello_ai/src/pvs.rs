@@ -0,0 +1,152 @@
+use crate::{AI, AIInfo, SearchResult, INFINITY};
+use crate::deepening::{Deepening, DeepeningAI, Node, Search};
+use crate::transposition::{Bound, Entry};
+use othello_game::{convert, Board, Game, GameRepr, Move, Score};
+use othello_game::bitboardgame::BitBoardBoard;
+
+/**
+ * Principal variation search, also known as NegaScout.  The first move in each position is searched
+ * with the full alpha-beta window.  With good move ordering it is usually the best, so the other
+ * moves are only searched with a null window, to show they are no better; one that turns out to be
+ * better is searched again with the full window.
+ *
+ * Like `AlphaBetaAI`, it deepens iteratively within its limits, and keeps a transposition table.
+ * Its search also returns the principal variation: the line of play it expects.
+ */
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PvsAI {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub deepening: Deepening,
+}
+
+impl PvsAI {
+    /**
+     * An AI that searches to the given depth, with no time or node limit.
+     */
+    pub fn new(max_depth: usize) -> Self {
+        PvsAI { deepening: Deepening::new(max_depth) }
+    }
+
+    /**
+     * Search for the best move for the player to move, and the line of play that follows it.  The
+     * score is from their point of view.
+     */
+    pub fn search<B: Board>(&self, game: &GameRepr<B>) -> SearchResult {
+        self.deepening.search(game, |search, moves, depth, _| self.search_root(game, moves, depth, search))
+    }
+
+    /**
+     * Search each move to the given depth, returning None if the search ran out of budget.
+     */
+    fn search_root<B: Board>(&self, game: &GameRepr<B>, moves: &[Move], depth: usize, search: &mut Search) -> Option<SearchResult> {
+        if moves.is_empty() {
+            let (score, line) = self.pvs(&game.pass(), 1, depth, -INFINITY, INFINITY, search)?;
+            let pv = std::iter::once(Move::pass(game.next_turn)).chain(line).collect();
+            return Some(SearchResult { best_move: None, score: -score, depth, pv });
+        }
+
+        let mut result = SearchResult { best_move: None, score: -INFINITY, depth, pv: Vec::new() };
+        for &mov in moves {
+            let child = game.apply(mov);
+            let (score, line) = if result.best_move.is_none() {
+                self.pvs(&child, 1, depth - 1, -INFINITY, INFINITY, search)?
+            } else {
+                let alpha = result.score;
+                let (score, line) = self.pvs(&child, 1, depth - 1, -alpha - 1, -alpha, search)?;
+                if -score > alpha {
+                    self.pvs(&child, 1, depth - 1, -INFINITY, -alpha, search)?
+                } else {
+                    (score, line)
+                }
+            };
+            if result.best_move.is_none() || -score > result.score {
+                result.best_move = Some(mov);
+                result.score = -score;
+                result.pv = std::iter::once(mov).chain(line).collect();
+            }
+        }
+        Some(result)
+    }
+
+    /**
+     * The score of a position `ply` moves from the root, for the player to move, and the line that
+     * leads to it if the score is inside the window.  None if the search ran out of budget.
+     */
+    fn pvs<B: Board>(&self, game: &GameRepr<B>, ply: usize, depth: usize, alpha: Score, beta: Score, search: &mut Search) -> Option<(Score, Vec<Move>)> {
+        if search.spend() {
+            return None;
+        }
+
+        let mut moves = match Node::new(game, depth) {
+            Node::Leaf(score) => return Some((score, Vec::new())),
+            Node::Pass(after) => {
+                let (score, line) = self.pvs(&after, ply + 1, depth, -beta, -alpha, search)?;
+                let pv = std::iter::once(Move::pass(game.next_turn)).chain(line).collect();
+                return Some((-score, pv));
+            }
+            Node::Moves(moves) => moves,
+        };
+
+        /* The table would cut the principal variation short, so it only ends null window searches */
+        let key = game.zobrist_key();
+        let table_depth = depth.min(u8::MAX as usize) as u8;
+        let entry = search.probe(key);
+        let null_window = beta - alpha == 1;
+        if let Some(entry) = entry.filter(|entry| null_window && entry.cuts_off(table_depth, alpha, beta)) {
+            search.info.add_cutoff();
+            return Some((entry.score, Vec::new()));
+        }
+        search.orderer.order(game, &mut moves, ply, depth, entry.and_then(|entry| entry.best_move));
+
+        let mut best = (moves[0], -INFINITY);
+        let mut pv = Vec::new();
+        let mut alpha_now = alpha;
+        for (i, mov) in moves.into_iter().enumerate() {
+            let child = game.apply(mov);
+            let (score, line) = if i == 0 {
+                self.pvs(&child, ply + 1, depth - 1, -beta, -alpha_now, search)?
+            } else {
+                let (score, line) = self.pvs(&child, ply + 1, depth - 1, -alpha_now - 1, -alpha_now, search)?;
+                if -score > alpha_now && -score < beta {
+                    self.pvs(&child, ply + 1, depth - 1, -beta, -alpha_now, search)?
+                } else {
+                    (score, line)
+                }
+            };
+            let score = -score;
+            if score > best.1 {
+                best = (mov, score);
+                if score > alpha_now {
+                    pv = std::iter::once(mov).chain(line).collect();
+                }
+            }
+            if score >= beta {
+                search.orderer.add_cutoff(mov, ply, depth);
+                break;
+            }
+            alpha_now = alpha_now.max(score);
+        }
+
+        let bound = if best.1 <= alpha { Bound::Upper } else if best.1 >= beta { Bound::Lower } else { Bound::Exact };
+        search.table.store(Entry::new(key, table_depth, bound, best.1, Some(best.0)));
+        Some((best.1, pv))
+    }
+}
+
+impl DeepeningAI for PvsAI {
+    fn deepening_mut(&mut self) -> &mut Deepening {
+        &mut self.deepening
+    }
+}
+
+impl AI for PvsAI {
+    fn choose_move(&self, game: &dyn Game) -> Option<Move> {
+        let game: GameRepr<BitBoardBoard> = convert(game);
+        self.search(&game).best_move
+    }
+
+    fn info(&self) -> Option<AIInfo> {
+        Some(self.deepening.info())
+    }
+}
@@ -3,7 +3,8 @@ use rand::seq::SliceRandom;
 use crate::AI;
 use othello_game::{Game, Move};
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RandomAI {
 }
 
@@ -0,0 +1,165 @@
+/*!
+ * A transposition table, remembering the results of searching positions so that a position reached
+ * by more than one sequence of moves is only searched once.
+ *
+ * The table has a fixed number of entries, and a position's Zobrist key decides which entry it goes
+ * in.  When two positions want the same entry, the new one replaces the old one if the old one is
+ * from an earlier search, or wasn't searched as deeply.
+ */
+
+use std::fmt::{Debug, Formatter};
+
+use othello_game::{Move, Score};
+
+/**
+ * How a stored score relates to the true score of the position.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Bound {
+    Exact,
+    /**
+     * The search failed high: the true score is at least this much.
+     */
+    Lower,
+    /**
+     * The search failed low: the true score is at most this much.
+     */
+    Upper,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Entry {
+    pub key: u64,
+    /**
+     * The depth the position was searched to.
+     */
+    pub depth: u8,
+    pub bound: Bound,
+    /**
+     * The score for the player to move.
+     */
+    pub score: Score,
+    /**
+     * The best move found, or None if the player to move had to pass.
+     */
+    pub best_move: Option<Move>,
+}
+
+impl Entry {
+    pub fn new(key: u64, depth: u8, bound: Bound, score: Score, best_move: Option<Move>) -> Self {
+        Entry { key, depth, bound, score, best_move }
+    }
+
+    /**
+     * Whether the score settles the position for a search to the given depth and window.
+     */
+    pub fn cuts_off(&self, depth: u8, alpha: Score, beta: Score) -> bool {
+        self.depth >= depth && match self.bound {
+            Bound::Exact => true,
+            Bound::Lower => self.score >= beta,
+            Bound::Upper => self.score <= alpha,
+        }
+    }
+}
+
+/**
+ * A fixed-size table of search results.  The entries aren't allocated until the table is first
+ * resized, so an AI can be created and cloned cheaply.
+ */
+#[derive(Clone, Default)]
+pub struct TranspositionTable {
+    /* Each entry with the search it was stored in */
+    entries: Vec<Option<(Entry, u8)>>,
+    generation: u8,
+}
+
+impl TranspositionTable {
+    /**
+     * The number of entries AIs use unless they are given another size.
+     */
+    pub const DEFAULT_SIZE: usize = 1 << 16;
+
+    /**
+     * A table with at least the given number of entries, rounded up to a power of two.
+     */
+    pub fn new(num_entries: usize) -> Self {
+        let mut table = TranspositionTable::default();
+        table.resize(num_entries);
+        table
+    }
+
+    /**
+     * The number of entries.
+     */
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /**
+     * Change the number of entries, rounded up to a power of two.  The table is cleared if its size
+     * changes.
+     */
+    pub fn resize(&mut self, num_entries: usize) {
+        let num_entries = num_entries.max(1).next_power_of_two();
+        if self.entries.len() != num_entries {
+            self.entries = vec![None; num_entries];
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.fill(None);
+    }
+
+    /**
+     * Start a new search.  Entries from earlier searches are kept, but are replaced first.
+     */
+    pub fn new_search(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    fn index(&self, key: u64) -> usize {
+        key as usize & (self.len() - 1)
+    }
+
+    /**
+     * The entry for a position, if there is one.
+     */
+    pub fn probe(&self, key: u64) -> Option<Entry> {
+        if self.is_empty() {
+            return None;
+        }
+        self.entries[self.index(key)]
+            .map(|(entry, _)| entry)
+            .filter(|entry| entry.key == key)
+    }
+
+    /**
+     * Store an entry, unless its slot holds a deeper search of another position from this search.
+     */
+    pub fn store(&mut self, entry: Entry) {
+        if self.is_empty() {
+            return;
+        }
+        let index = self.index(entry.key);
+        let generation = self.generation;
+        let replace = match &self.entries[index] {
+            None => true,
+            Some((old, old_generation)) => old.key == entry.key || *old_generation != generation || entry.depth >= old.depth,
+        };
+        if replace {
+            self.entries[index] = Some((entry, generation));
+        }
+    }
+}
+
+impl Debug for TranspositionTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TranspositionTable")
+            .field("len", &self.len())
+            .finish()
+    }
+}
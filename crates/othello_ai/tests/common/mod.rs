@@ -0,0 +1,23 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use othello_game::{Game, GameRepr};
+
+/**
+ * A position reached by playing random moves from the start.
+ */
+pub fn random_position(seed: u64, num_moves: usize) -> GameRepr {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut game: GameRepr = GameRepr::new();
+    for _ in 0..num_moves {
+        if game.is_game_over() {
+            break;
+        }
+        game = match game.valid_moves(game.next_turn).choose(&mut rng) {
+            Some(mov) => game.apply(*mov),
+            None => game.pass(),
+        };
+    }
+    game
+}
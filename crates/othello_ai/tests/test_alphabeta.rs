@@ -0,0 +1,65 @@
+mod common;
+
+use othello_ai::{AlphaBetaAI, DeepeningAI, AI};
+use othello_ai::ordering::MoveOrdering;
+use othello_game::{Game, GameRepr};
+
+use common::random_position;
+
+#[test]
+fn test_depth_limit() {
+    let game: GameRepr = GameRepr::new();
+    let ai = AlphaBetaAI::new(4);
+    let mov = ai.choose_move(&game);
+    assert!(mov.is_some_and(|mov| game.is_valid_move(mov)));
+    assert_eq!(4, ai.info().unwrap().last_depth.get());
+}
+
+#[test]
+fn test_node_limit() {
+    let game = random_position(1, 10);
+    let ai = AlphaBetaAI::new(60).with_node_limit(5000);
+    let result = ai.search(&game);
+    assert!(ai.info().unwrap().last_nodes_searched.get() <= 5001);
+    assert!(result.depth >= 1 && result.depth < 60);
+
+    /* The move is the one found by the last complete depth */
+    let full = AlphaBetaAI::new(result.depth).search(&game);
+    assert_eq!(full.score, result.score);
+}
+
+#[test]
+fn test_transposition_table() {
+    let game = random_position(2, 12);
+    let ai = AlphaBetaAI::new(5);
+    let result = ai.search(&game);
+    let info = ai.info().unwrap();
+    assert!(info.table_probes.get() > 0);
+    assert!(info.table_hits.get() > 0 && info.table_hits.get() <= info.table_probes.get());
+    assert!(info.table_cutoffs.get() > 0 && info.table_cutoffs.get() <= info.table_hits.get());
+
+    /* A tiny table gives the same answer */
+    let small = AlphaBetaAI::new(5).with_table_size(1).search(&game);
+    assert_eq!(result.score, small.score);
+
+    /* Searching again uses what was learned the first time */
+    let nodes = info.last_nodes_searched.get();
+    let again = ai.search(&game);
+    assert_eq!(result.score, again.score);
+    assert!(ai.info().unwrap().last_nodes_searched.get() < nodes);
+}
+
+#[test]
+fn test_move_ordering() {
+    let (mut ordered_nodes, mut generated_nodes) = (0, 0);
+    for seed in 0..10 {
+        let game = random_position(seed, 16);
+        let ordered = AlphaBetaAI::new(6);
+        let generated = AlphaBetaAI::new(6).with_move_ordering(MoveOrdering::Generated);
+        assert_eq!(generated.search(&game).score, ordered.search(&game).score, "position\n{game:?}");
+        ordered_nodes += ordered.info().unwrap().last_nodes_searched.get();
+        generated_nodes += generated.info().unwrap().last_nodes_searched.get();
+    }
+    println!("{ordered_nodes} nodes with move ordering, {generated_nodes} without");
+    assert!(ordered_nodes < generated_nodes);
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use othello_ai::{evaluate_immediate, AlphaBetaAI, Deepening, MtdfAI, PvsAI, SearchResult};
+use othello_ai::ordering::MoveOrdering;
+use othello_game::{Colour, Game, GameRepr, Score};
+
+use common::random_position;
+
+type SearchFn = Box<dyn Fn(&GameRepr) -> SearchResult>;
+
+/**
+ * Each of the searches that deepen iteratively, by name, with the same limits.
+ */
+fn searches(deepening: &Deepening) -> Vec<(&'static str, SearchFn)> {
+    let alphabeta = AlphaBetaAI { deepening: deepening.clone(), move_ordering: MoveOrdering::Heuristic };
+    let pvs = PvsAI { deepening: deepening.clone() };
+    let mtdf = MtdfAI { deepening: deepening.clone() };
+    vec![
+        ("alpha-beta", Box::new(move |game| alphabeta.search(game))),
+        ("PVS", Box::new(move |game| pvs.search(game))),
+        ("MTD(f)", Box::new(move |game| mtdf.search(game))),
+    ]
+}
+
+/**
+ * A plain negamax search without pruning, to check the scores against.
+ */
+fn negamax(game: &GameRepr, depth: usize) -> Score {
+    if game.is_game_over() || depth == 0 {
+        return evaluate_immediate(game, game.next_turn);
+    }
+    let moves = game.valid_moves(game.next_turn);
+    if moves.is_empty() {
+        return -negamax(&game.pass(), depth);
+    }
+    moves.into_iter().map(|mov| -negamax(&game.apply(mov), depth - 1)).max().unwrap()
+}
+
+#[test]
+fn test_scores_match_negamax() {
+    for seed in 0..10 {
+        let game = random_position(seed, 20 + seed as usize);
+        let expected = negamax(&game, 3);
+        for (name, search) in searches(&Deepening::new(3)) {
+            let result = search(&game);
+            assert_eq!(expected, result.score, "{name} in position\n{game:?}");
+            assert_eq!(3, result.depth, "{name}");
+
+            /* The move may differ from one search to another, but it must be as good */
+            if let Some(mov) = result.best_move {
+                assert_eq!(result.score, -negamax(&game.apply(mov), 2), "{name} playing {mov} in position\n{game:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_pass() {
+    /* White has no move, but Black does after White passes */
+    let mut game: GameRepr = "○●·".try_into().unwrap();
+    game.next_turn = Colour::White;
+    for (name, search) in searches(&Deepening::new(2)) {
+        let result = search(&game);
+        assert_eq!(None, result.best_move, "{name}");
+        assert_eq!(-3, result.score, "{name}");
+    }
+}
+
+#[test]
+fn test_stops_at_end_of_game() {
+    /* Only a few empty squares are left, so searching deeper than that gains nothing */
+    let game: GameRepr = "\
+        ○○○○○○○○\n\
+        ○○○○○○○○\n\
+        ○○○○○○○○\n\
+        ○○○○○○○○\n\
+        ●●●●●●●●\n\
+        ●●●●●●●●\n\
+        ●●●●●●○○\n\
+        ●●●●●···".try_into().unwrap();
+    for (name, search) in searches(&Deepening::new(60)) {
+        let result = search(&game);
+        assert_eq!(3, result.depth, "{name}");
+        assert!(result.best_move.is_some(), "{name}");
+    }
+}
+
+#[test]
+fn test_time_limit() {
+    let game: GameRepr = GameRepr::new();
+    let mut deepening = Deepening::new(60);
+    deepening.max_time = Some(Duration::from_millis(100));
+    for (name, search) in searches(&deepening) {
+        let t0 = Instant::now();
+        let result = search(&game);
+        assert!(t0.elapsed() < Duration::from_secs(1), "{name} took {:?}", t0.elapsed());
+        assert!(result.best_move.is_some_and(|mov| game.is_valid_move(mov)), "{name}");
+
+        /* Not every depth could be searched, but at least the first was */
+        assert!((1..60).contains(&result.depth), "{name} reached depth {}", result.depth);
+    }
+}
+
+#[test]
+fn test_first_depth_always_completes() {
+    let game: GameRepr = GameRepr::new();
+    let mut deepening = Deepening::new(10);
+    deepening.max_nodes = Some(0);
+    for (name, search) in searches(&deepening) {
+        let result = search(&game);
+        assert_eq!(1, result.depth, "{name}");
+        assert!(result.best_move.is_some(), "{name}");
+    }
+}
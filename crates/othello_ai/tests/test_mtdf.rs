@@ -0,0 +1,23 @@
+mod common;
+
+use othello_ai::{AlphaBetaAI, MtdfAI};
+use othello_game::Game;
+
+use common::random_position;
+
+#[test]
+fn test_same_score_as_alphabeta() {
+    for seed in 0..20 {
+        let game = random_position(seed, 10 + 2 * seed as usize);
+        let mtdf = MtdfAI::new(6).search(&game);
+        let alphabeta = AlphaBetaAI::new(6).search(&game);
+        assert_eq!(alphabeta.score, mtdf.score, "position\n{game:?}");
+        assert_eq!(alphabeta.depth, mtdf.depth);
+
+        /* The move may differ from alpha-beta's, but it must be as good */
+        let mov = mtdf.best_move.expect("a move");
+        assert!(game.is_valid_move(mov));
+        let after = AlphaBetaAI::new(5).search(&game.apply(mov));
+        assert_eq!(mtdf.score, -after.score, "{mov} in position\n{game:?}");
+    }
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use othello_ai::{evaluate_immediate, AlphaBetaAI, PvsAI, AI};
+use othello_game::{Colour, Game, GameRepr};
+
+use common::random_position;
+
+#[test]
+fn test_same_score_as_alphabeta() {
+    let (mut pvs_nodes, mut alphabeta_nodes) = (0, 0);
+    for seed in 0..20 {
+        let game = random_position(seed, 10 + seed as usize);
+        let pvs = PvsAI::new(6);
+        let alphabeta = AlphaBetaAI::new(6);
+        let pvs_result = pvs.search(&game);
+        let alphabeta_result = alphabeta.search(&game);
+        assert_eq!(alphabeta_result.score, pvs_result.score, "position\n{game:?}");
+        assert_eq!(6, pvs_result.depth);
+
+        pvs_nodes += pvs.info().unwrap().last_nodes_searched.get();
+        alphabeta_nodes += alphabeta.info().unwrap().last_nodes_searched.get();
+    }
+    println!("{pvs_nodes} nodes with PVS, {alphabeta_nodes} with alpha-beta");
+    assert!(pvs_nodes < alphabeta_nodes);
+}
+
+#[test]
+fn test_principal_variation() {
+    for seed in 0..10 {
+        let game = random_position(seed, 20);
+        let result = PvsAI::new(5).search(&game);
+        assert_eq!(result.best_move, result.pv.first().copied().filter(|mov| !mov.is_pass()));
+
+        /* The line is legal, reaches the depth searched, and leads to the score */
+        let mut end = game.clone();
+        for mov in &result.pv {
+            assert!(end.is_valid_move(*mov), "{mov} in {:?}", result.pv);
+            end = if mov.is_pass() { end.pass() } else { end.apply(*mov) };
+        }
+        let moves_played = result.pv.iter().filter(|mov| !mov.is_pass()).count();
+        assert!(moves_played == 5 || end.is_game_over(), "{:?}", result.pv);
+        assert_eq!(result.score, evaluate_immediate(&end, game.next_turn));
+    }
+}
+
+#[test]
+fn test_pass_in_principal_variation() {
+    /* White has no move, but Black does after White passes */
+    let mut game: GameRepr = "○●·".try_into().unwrap();
+    game.next_turn = Colour::White;
+    let result = PvsAI::new(2).search(&game);
+    assert_eq!(2, result.pv.len());
+    assert!(result.pv[0].is_pass());
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "serde")]
+
+use std::time::Duration;
+
+use othello_ai::{AlphaBetaAI, DeepeningAI, MinimaxAI, MtdfAI, PvsAI, AI};
+use othello_ai::ordering::MoveOrdering;
+use othello_game::GameRepr;
+
+#[test]
+fn test_alphabeta_config() {
+    let ai = AlphaBetaAI::new(4);
+    let json = serde_json::to_string(&ai).unwrap();
+    assert_eq!(r#"{"max_depth":4}"#, json);
+    let ai2: AlphaBetaAI = serde_json::from_str(&json).unwrap();
+    assert_eq!(4, ai2.deepening.max_depth);
+}
+
+#[test]
+fn test_alphabeta_limits_config() {
+    let ai = AlphaBetaAI::new(20).with_time_limit(Duration::from_millis(500)).with_node_limit(100000);
+    let json = serde_json::to_string(&ai).unwrap();
+    assert_eq!(r#"{"max_depth":20,"max_time":{"secs":0,"nanos":500000000},"max_nodes":100000}"#, json);
+    let ai2: AlphaBetaAI = serde_json::from_str(&json).unwrap();
+    assert_eq!(Some(Duration::from_millis(500)), ai2.deepening.max_time);
+    assert_eq!(Some(100000), ai2.deepening.max_nodes);
+}
+
+#[test]
+fn test_alphabeta_move_ordering_config() {
+    let ai = AlphaBetaAI::new(4).with_move_ordering(MoveOrdering::Generated);
+    let json = serde_json::to_string(&ai).unwrap();
+    assert_eq!(r#"{"max_depth":4,"move_ordering":"Generated"}"#, json);
+    let ai2: AlphaBetaAI = serde_json::from_str(&json).unwrap();
+    assert_eq!(MoveOrdering::Generated, ai2.move_ordering);
+}
+
+#[test]
+fn test_minimax_config() {
+    let ai = MinimaxAI::new(2);
+    let game: GameRepr = GameRepr::new();
+    ai.choose_move(&game);
+    assert!(ai.info().unwrap().total_nodes_searched.get() > 0);
+
+    /* Search statistics aren't saved */
+    let json = serde_json::to_string(&ai).unwrap();
+    assert_eq!(r#"{"max_depth":2}"#, json);
+    let ai2: MinimaxAI = serde_json::from_str(&json).unwrap();
+    assert_eq!(2, ai2.max_depth);
+    assert_eq!(0, ai2.info().unwrap().total_nodes_searched.get());
+}
+
+#[test]
+fn test_pvs_config() {
+    let ai = PvsAI::new(6).with_table_size(1024);
+    let json = serde_json::to_string(&ai).unwrap();
+    assert_eq!(r#"{"max_depth":6,"table_size":1024}"#, json);
+    let ai2: PvsAI = serde_json::from_str(&json).unwrap();
+    assert_eq!(6, ai2.deepening.max_depth);
+    assert_eq!(Some(1024), ai2.deepening.table_size);
+}
+
+#[test]
+fn test_mtdf_config() {
+    let ai = MtdfAI::new(6).with_node_limit(50000);
+    let json = serde_json::to_string(&ai).unwrap();
+    assert_eq!(r#"{"max_depth":6,"max_nodes":50000}"#, json);
+    let ai2: MtdfAI = serde_json::from_str(&json).unwrap();
+    assert_eq!(6, ai2.deepening.max_depth);
+    assert_eq!(Some(50000), ai2.deepening.max_nodes);
+}
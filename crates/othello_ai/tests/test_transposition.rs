@@ -0,0 +1,72 @@
+use othello_ai::transposition::{Bound, Entry, TranspositionTable};
+use othello_game::{Colour, Move};
+
+fn mov(row: i8, col: i8) -> Option<Move> {
+    Some(Move { player: Colour::Black, row, col })
+}
+
+#[test]
+fn test_size() {
+    let table = TranspositionTable::new(1000);
+    assert_eq!(1024, table.len());
+
+    /* Nothing is stored until the table has entries */
+    let mut table = TranspositionTable::default();
+    assert!(table.is_empty());
+    table.store(Entry::new(1, 1, Bound::Exact, 0, None));
+    assert_eq!(None, table.probe(1));
+}
+
+#[test]
+fn test_probe() {
+    let mut table = TranspositionTable::new(16);
+    let entry = Entry::new(0x1234, 3, Bound::Lower, 5, mov(2, 3));
+    table.store(entry);
+    assert_eq!(Some(entry), table.probe(0x1234));
+
+    /* Another position with the same slot */
+    assert_eq!(None, table.probe(0x1234 + 16));
+
+    table.clear();
+    assert_eq!(None, table.probe(0x1234));
+}
+
+#[test]
+fn test_replacement() {
+    let mut table = TranspositionTable::new(16);
+    let deep = Entry::new(1, 5, Bound::Exact, 5, mov(2, 3));
+    let shallow = Entry::new(17, 2, Bound::Exact, -3, mov(4, 5));
+
+    /* A shallower search doesn't replace a deeper one from the same search */
+    table.store(deep);
+    table.store(shallow);
+    assert_eq!(Some(deep), table.probe(1));
+    assert_eq!(None, table.probe(17));
+
+    /* The same position is always replaced */
+    let again = Entry::new(1, 1, Bound::Upper, 0, None);
+    table.store(again);
+    assert_eq!(Some(again), table.probe(1));
+
+    /* Entries from an earlier search are replaced */
+    table.store(deep);
+    table.new_search();
+    table.store(shallow);
+    assert_eq!(None, table.probe(1));
+    assert_eq!(Some(shallow), table.probe(17));
+}
+
+#[test]
+fn test_cuts_off() {
+    let exact = Entry::new(1, 4, Bound::Exact, 10, None);
+    assert!(exact.cuts_off(4, -100, 100));
+    assert!(!exact.cuts_off(5, -100, 100));
+
+    let lower = Entry::new(1, 4, Bound::Lower, 10, None);
+    assert!(lower.cuts_off(3, 0, 10));
+    assert!(!lower.cuts_off(3, 0, 11));
+
+    let upper = Entry::new(1, 4, Bound::Upper, 10, None);
+    assert!(upper.cuts_off(3, 10, 20));
+    assert!(!upper.cuts_off(3, 9, 20));
+}
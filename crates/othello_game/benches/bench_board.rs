@@ -1,8 +1,13 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
 
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use othello_game::bitboard::BitBoard;
 use othello_game::bitboardgame::BitBoardBoard;
+use othello_game::movegen::FillBackend;
 use othello_game::{Board, Colour, convert_board, random_board};
 use othello_game::default::DefaultBoard;
+use othello_game::tableboard::TableBoard;
 
 pub fn board_benchmark(c: &mut Criterion) {
     const NUM_BOARDS: usize = 100;
@@ -18,16 +23,65 @@ pub fn board_benchmark(c: &mut Criterion) {
         });
     });
 
-    let boards: Vec<DefaultBoard> = boards.iter().map(convert_board).collect();
+    let default_boards: Vec<DefaultBoard> = boards.iter().map(convert_board).collect();
 
     c.bench_function("default board", |b| {
         b.iter(|| {
-            for bb in &boards {
+            for bb in &default_boards {
                 black_box(bb.moves(Colour::Black));
                 black_box(bb.moves(Colour::White));
             }
         });
     });
+
+    let table_boards: Vec<TableBoard> = boards.iter().map(convert_board).collect();
+
+    c.bench_function("apply bitboard board", |b| {
+        b.iter(|| apply_all(&boards));
+    });
+
+    c.bench_function("apply table board", |b| {
+        b.iter(|| apply_all(&table_boards));
+    });
+
+    c.bench_function("apply default board", |b| {
+        b.iter(|| apply_all(&default_boards));
+    });
+
+    let positions: Vec<(BitBoard, BitBoard)> = boards.iter()
+        .map(|bb| (bb.blacks(), bb.whites()))
+        .collect();
+
+    for backend in FillBackend::ALL.into_iter().filter(|b| b.is_available()) {
+        c.bench_function(&format!("moves {backend:?}"), |b| {
+            b.iter(|| {
+                for (blacks, whites) in &positions {
+                    black_box(backend.moves(*blacks, *whites));
+                    black_box(backend.moves(*whites, *blacks));
+                }
+            });
+        });
+
+        c.bench_function(&format!("flips {backend:?}"), |b| {
+            b.iter(|| {
+                for (blacks, whites) in &positions {
+                    for mov in backend.moves(*blacks, *whites) {
+                        black_box(backend.flips(*blacks, *whites, BitBoard::from(mov)));
+                    }
+                }
+            });
+        });
+    }
+}
+
+fn apply_all<B: Board>(boards: &[B]) {
+    for board in boards {
+        for colour in [Colour::Black, Colour::White] {
+            for mov in board.moves(colour) {
+                black_box(board.apply(mov));
+            }
+        }
+    }
 }
 
 criterion_group!(benches, board_benchmark);
@@ -1,8 +1,14 @@
 use std::fmt::{Debug, Display, Formatter, Write};
 use std::ops::{BitAnd, BitOr, BitAndAssign, BitOrAssign, Not};
 
-#[derive(Clone, Copy)]
-pub(crate) enum ShiftDir {
+use crate::symmetry::Transform;
+
+/**
+ * The eight directions pieces can be flanked in.  Up is towards row 0, and Left is towards
+ * column 0.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ShiftDir {
     Up,
     Down,
     Left,
@@ -14,7 +20,10 @@ pub(crate) enum ShiftDir {
 }
 
 impl ShiftDir {
-    pub(crate) fn shift(&self) -> i8 {
+    /**
+     * The change in bit position when moving one square in this direction.
+     */
+    pub fn shift(&self) -> i8 {
         match self {
             ShiftDir::Up => -8,
             ShiftDir::Down => 8,
@@ -27,7 +36,7 @@ impl ShiftDir {
         }
     }
 
-    pub(crate) fn reverse(&self) -> ShiftDir {
+    pub fn reverse(&self) -> ShiftDir {
         match self {
             ShiftDir::Up => ShiftDir::Down,
             ShiftDir::Down => ShiftDir::Up,
@@ -41,32 +50,71 @@ impl ShiftDir {
     }
 }
 
-pub(crate) const SHIFT_DIRS: &[ShiftDir] = &[
+pub const SHIFT_DIRS: &[ShiftDir] = &[
     ShiftDir::Up, ShiftDir::Down, ShiftDir::Left, ShiftDir::Right,
     ShiftDir::UpLeft, ShiftDir::UpRight, ShiftDir::DownLeft, ShiftDir::DownRight,
 ];
 
-#[derive(Clone, Copy, Default, PartialEq)]
+/**
+ * A set of squares on an 8x8 board, stored as one bit per square.  Square (row, col) is bit
+ * number `row * 8 + col`, so row 0 is the least significant byte.
+ */
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
 pub struct BitBoard(u64);
 
 impl BitBoard {
-    pub(crate) fn new() -> BitBoard {
+    /** The four corner squares. */
+    pub const CORNERS: BitBoard = BitBoard(0x8100000000000081);
+
+    /** All squares on the edge of the board, including the corners. */
+    pub const EDGES: BitBoard = BitBoard(0xFF818181818181FF);
+
+    /** The squares diagonally adjacent to the corners, such as B2. */
+    pub const X_SQUARES: BitBoard = BitBoard(0x0042000000004200);
+
+    /** The edge squares next to the corners, such as A2 and B1. */
+    pub const C_SQUARES: BitBoard = BitBoard(0x4281000000008142);
+
+    pub const fn new() -> BitBoard {
         BitBoard(0)
     }
 
-    pub(crate) fn is_empty(&self) -> bool {
+    pub const fn from_bits(bits: u64) -> BitBoard {
+        BitBoard(bits)
+    }
+
+    pub const fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /**
+     * The bitboard with just the given square set, where squares are numbered from 0 to 63 as
+     * `row * 8 + col`.
+     */
+    pub fn from_square(square: usize) -> BitBoard {
+        assert!(square < 64, "square {square} out of range");
+        BitBoard(1 << square)
+    }
+
+    pub const fn is_empty(&self) -> bool {
         self.0 == 0
     }
 
-    pub(crate) fn bit(&self, row: i8, col: i8) -> bool {
+    pub fn bit(&self, row: i8, col: i8) -> bool {
         ((self.0 >> (row * 8 + col)) & 1) != 0
     }
 
-    pub(crate) fn count(&self) -> u32 {
+    /**
+     * The number of squares set.
+     */
+    pub fn count(&self) -> u32 {
         self.0.count_ones()
     }
 
-    pub(crate) fn next_bit(&self) -> BitBoard {
+    /**
+     * The lowest set square, as a bitboard, or an empty bitboard if none are set.
+     */
+    pub fn next_bit(&self) -> BitBoard {
         if self.0 == 0 {
             return *self;
         }
@@ -75,7 +123,10 @@ impl BitBoard {
         BitBoard(lsb)
     }
 
-    pub(crate) fn pop_next_bit(&mut self) -> BitBoard {
+    /**
+     * Remove the lowest set square, returning it as a bitboard.
+     */
+    pub fn pop_next_bit(&mut self) -> BitBoard {
         if self.0 == 0 {
             return *self;
         }
@@ -85,13 +136,35 @@ impl BitBoard {
         BitBoard(lsb)
     }
 
-    pub(crate) fn to_bit_pos(self) -> (i8, i8) {
+    /**
+     * The number of the lowest set square, or 64 if the bitboard is empty.
+     */
+    pub fn to_square(self) -> usize {
+        self.0.trailing_zeros() as usize
+    }
+
+    /**
+     * The (row, col) of the lowest set square.  The bitboard must not be empty.
+     */
+    pub fn to_bit_pos(self) -> (i8, i8) {
+        debug_assert!(!self.is_empty(), "empty bitboard has no position");
         let bit_num = self.0.trailing_zeros() as i8;
         (bit_num >> 3, bit_num & 7)
     }
 
+    /**
+     * Iterate over the set squares as (row, col), from the lowest square number to the highest.
+     */
+    pub fn iter(&self) -> Squares {
+        Squares(*self)
+    }
+
+    /**
+     * Move every square one step in the given direction.  Squares moved off the board are lost.
+     */
     #[inline(always)]
-    pub(crate) fn shift(&self, shift: i8) -> BitBoard {
+    pub fn shift(&self, dir: ShiftDir) -> BitBoard {
+        let shift = dir.shift();
         let x = self.0;
         let mut x = if shift < 0 {
             x >> (-shift)
@@ -107,6 +180,103 @@ impl BitBoard {
     }
 }
 
+pub struct Squares(BitBoard);
+
+impl Iterator for Squares {
+    type Item = (i8, i8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0.is_empty() {
+            return None;
+        }
+        Some(self.0.pop_next_bit().to_bit_pos())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.0.count() as usize;
+        (count, Some(count))
+    }
+}
+
+impl ExactSizeIterator for Squares {}
+
+impl IntoIterator for BitBoard {
+    type Item = (i8, i8);
+    type IntoIter = Squares;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<u64> for BitBoard {
+    fn from(value: u64) -> Self {
+        BitBoard(value)
+    }
+}
+
+/*
+ * Board symmetries, adapted from https://www.chessprogramming.org/Flipping_Mirroring_and_Rotating.
+ * Rows in a bitboard correspond to bytes, so flipping vertically just swaps the bytes.
+ */
+impl BitBoard {
+    pub fn flip_vertical(self) -> BitBoard {
+        BitBoard(self.0.swap_bytes())
+    }
+
+    pub fn flip_horizontal(self) -> BitBoard {
+        const K1: u64 = 0x5555555555555555;
+        const K2: u64 = 0x3333333333333333;
+        const K4: u64 = 0x0F0F0F0F0F0F0F0F;
+        let mut x = self.0;
+        x = ((x >> 1) & K1) | ((x & K1) << 1);
+        x = ((x >> 2) & K2) | ((x & K2) << 2);
+        x = ((x >> 4) & K4) | ((x & K4) << 4);
+        BitBoard(x)
+    }
+
+    pub fn flip_diagonal(self) -> BitBoard {
+        const K1: u64 = 0x5500550055005500;
+        const K2: u64 = 0x3333000033330000;
+        const K4: u64 = 0x0F0F0F0F00000000;
+        let mut x = self.0;
+        let t = K4 & (x ^ (x << 28));
+        x ^= t ^ (t >> 28);
+        let t = K2 & (x ^ (x << 14));
+        x ^= t ^ (t >> 14);
+        let t = K1 & (x ^ (x << 7));
+        x ^= t ^ (t >> 7);
+        BitBoard(x)
+    }
+
+    pub fn flip_anti_diagonal(self) -> BitBoard {
+        const K1: u64 = 0xAA00AA00AA00AA00;
+        const K2: u64 = 0xCCCC0000CCCC0000;
+        const K4: u64 = 0xF0F0F0F00F0F0F0F;
+        let mut x = self.0;
+        let t = x ^ (x << 36);
+        x ^= K4 & (t ^ (x >> 36));
+        let t = K2 & (x ^ (x << 18));
+        x ^= t ^ (t >> 18);
+        let t = K1 & (x ^ (x << 9));
+        x ^= t ^ (t >> 9);
+        BitBoard(x)
+    }
+
+    pub fn transform(self, transform: Transform) -> BitBoard {
+        match transform {
+            Transform::Identity => self,
+            Transform::Rotate90 => self.flip_diagonal().flip_horizontal(),
+            Transform::Rotate180 => BitBoard(self.0.reverse_bits()),
+            Transform::Rotate270 => self.flip_diagonal().flip_vertical(),
+            Transform::FlipVertical => self.flip_vertical(),
+            Transform::FlipHorizontal => self.flip_horizontal(),
+            Transform::FlipDiagonal => self.flip_diagonal(),
+            Transform::FlipAntiDiagonal => self.flip_anti_diagonal(),
+        }
+    }
+}
+
 impl From<(i8, i8)> for BitBoard {
     fn from(value: (i8, i8)) -> Self {
         let mut bb = BitBoard::new();
@@ -225,10 +395,11 @@ impl Not for BitBoard {
 }
 
 /**
- * A regular dumb7fill, adapted from https://www.chessprogramming.org/Dumb7Fill.
+ * A regular dumb7fill, adapted from https://www.chessprogramming.org/Dumb7Fill.  Returns the
+ * squares of `gen`, plus those reached from them by moving through `pro` in the given direction.
  */
 #[inline(always)]
-pub(crate) fn dumb7fill(mut gen: BitBoard, pro: BitBoard, shift: i8) -> BitBoard {
+pub fn dumb7fill(mut gen: BitBoard, pro: BitBoard, shift: ShiftDir) -> BitBoard {
     let mut flood = gen;
     for _ in 1..7 {
         gen = gen.shift(shift) & pro;
@@ -238,10 +409,11 @@ pub(crate) fn dumb7fill(mut gen: BitBoard, pro: BitBoard, shift: i8) -> BitBoard
 }
 
 /**
- * An occluded dumb7fill, adapted from https://www.chessprogramming.org/Dumb7Fill.
+ * An occluded dumb7fill, adapted from https://www.chessprogramming.org/Dumb7Fill.  Like
+ * `dumb7fill`, but without the original squares of `gen`.
  */
 #[inline(always)]
-pub(crate) fn dumb7fill_occluded(mut gen: BitBoard, pro: BitBoard, shift: i8) -> BitBoard {
+pub fn dumb7fill_occluded(mut gen: BitBoard, pro: BitBoard, shift: ShiftDir) -> BitBoard {
     let mut flood = BitBoard::new();
     for _ in 1..7 {
         gen = gen.shift(shift) & pro;
@@ -283,11 +455,44 @@ mod test {
     fn test_shift() {
         let bb = BitBoard::from(&[(4, 0), (3, 3)]);
 
-        assert_eq!(BitBoard::from(&[(3, 0), (2, 3)]), bb.shift(ShiftDir::Up.shift()));
-        assert_eq!(BitBoard::from(&[(5, 0), (4, 3)]), bb.shift(ShiftDir::Down.shift()));
+        assert_eq!(BitBoard::from(&[(3, 0), (2, 3)]), bb.shift(ShiftDir::Up));
+        assert_eq!(BitBoard::from(&[(5, 0), (4, 3)]), bb.shift(ShiftDir::Down));
+
+        assert_eq!(BitBoard::from((3, 2)), bb.shift(ShiftDir::Left));
+        assert_eq!(BitBoard::from(&[(4, 1), (3, 4)]), bb.shift(ShiftDir::Right));
+    }
 
-        assert_eq!(BitBoard::from((3, 2)), bb.shift(ShiftDir::Left.shift()));
-        assert_eq!(BitBoard::from(&[(4, 1), (3, 4)]), bb.shift(ShiftDir::Right.shift()));
+    #[test]
+    fn test_transform() {
+        let squares = [(0, 1), (2, 5), (7, 3), (4, 4)];
+        let bb = BitBoard::from(&squares);
+        for t in Transform::ALL {
+            let expected: Vec<_> = squares.iter().map(|(r, c)| t.apply(*r, *c, 8)).collect();
+            assert_eq!(BitBoard::from(&expected[..]), bb.transform(t), "{t:?}");
+        }
+    }
+
+    #[test]
+    fn test_squares() {
+        let bb = BitBoard::from(&[(0, 6), (0, 0), (5, 2)]);
+        assert_eq!(vec![(0, 0), (0, 6), (5, 2)], bb.iter().collect::<Vec<_>>());
+        assert_eq!(3, bb.iter().len());
+        assert_eq!(0, BitBoard::new().into_iter().count());
+
+        assert_eq!(BitBoard::from((5, 2)), BitBoard::from_square(42));
+        assert_eq!(42, BitBoard::from((5, 2)).to_square());
+        assert_eq!((5, 2), BitBoard::from_square(42).to_bit_pos());
+        assert_eq!(BitBoard::from_bits(1 << 42), BitBoard::from_square(42));
+    }
+
+    #[test]
+    fn test_masks() {
+        assert_eq!(BitBoard::from(&[(0, 0), (0, 7), (7, 0), (7, 7)]), BitBoard::CORNERS);
+        assert_eq!(BitBoard::from(&[(1, 1), (1, 6), (6, 1), (6, 6)]), BitBoard::X_SQUARES);
+        assert_eq!(BitBoard::from(&[(0, 1), (1, 0), (0, 6), (1, 7), (6, 0), (7, 1), (6, 7), (7, 6)]), BitBoard::C_SQUARES);
+        assert_eq!(28, BitBoard::EDGES.count());
+        assert!(BitBoard::EDGES.iter().all(|(r, c)| r == 0 || r == 7 || c == 0 || c == 7));
+        assert_eq!(BitBoard::CORNERS, BitBoard::EDGES & BitBoard::CORNERS);
     }
 
     #[test]
@@ -295,7 +500,7 @@ mod test {
         let gen = BitBoard::from("X··X···X");
         let pro = BitBoard::from("·XXX··X·");
 
-        let filled = dumb7fill_occluded(gen, pro, -1);
+        let filled = dumb7fill_occluded(gen, pro, ShiftDir::Left);
         assert_eq!(BitBoard::from("·XX···X·"), filled);
     }
 }
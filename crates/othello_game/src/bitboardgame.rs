@@ -1,82 +1,128 @@
 use std::fmt::Debug;
-
-use crate::{bitboard, Board, Colour, Move, Pos, Score};
-use crate::bitboard::{BitBoard, dumb7fill, dumb7fill_occluded, SHIFT_DIRS, ShiftDir};
-
-#[derive(Clone, Debug, Default, PartialEq)]
-pub struct BitBoardBoard {
+use std::hash::{Hash, Hasher};
+
+use crate::{movegen, Board, Colour, Move, Pos, Score, zobrist};
+use crate::bitboard::BitBoard;
+use crate::symmetry::{canonical_by_key, transform_board, Transform};
+
+/**
+ * A board stored as a pair of bitboards.  Boards smaller than 8x8 use the top left corner of the
+ * bitboards.
+ */
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BitBoardBoard<const N: usize = 8> {
     blacks: BitBoard,
     whites: BitBoard,
+    key: u64,
 }
 
-fn moves_in_dir(mine: BitBoard, theirs: BitBoard, shift_dir: ShiftDir) -> BitBoard {
-    let moves = dumb7fill_occluded(mine, theirs, shift_dir.shift());
-    moves.shift(shift_dir.shift())
+/**
+ * The squares of an N x N board in the top left corner of a bitboard.
+ */
+const fn board_squares(size: usize) -> u64 {
+    let row = (1u64 << size) - 1;
+    let mut squares = 0;
+    let mut i = 0;
+    while i < size {
+        squares |= row << (i * 8);
+        i += 1;
+    }
+    squares
 }
 
-impl Board for BitBoardBoard {
-    type MoveSet = Moves;
-
-    fn is_valid_move(&self, mov: Move) -> bool {
-        let all_moves_bb = self.moves(mov.player).1;
-        let mov_bb = BitBoard::from((mov.row, mov.col));
-        !(all_moves_bb & mov_bb).is_empty()
+impl<const N: usize> BitBoardBoard<N> {
+    /**
+     * The squares that are on the board.
+     */
+    pub const SQUARES: BitBoard = {
+        assert!(N >= 4 && N <= 8 && N.is_multiple_of(2), "unsupported bitboard size");
+        BitBoard::from_bits(board_squares(N))
+    };
+
+    pub fn blacks(&self) -> BitBoard {
+        self.blacks
     }
 
-    fn moves(&self, player: Colour) -> Self::MoveSet {
-        let (mine, theirs) = match player {
-            Colour::Black => (self.blacks, self.whites),
-            Colour::White => (self.whites, self.blacks)
-        };
+    pub fn whites(&self) -> BitBoard {
+        self.whites
+    }
 
-        // macro_rules! do_multiple {
-        //     (self.$f:ident($arg1:expr, $arg2:expr); $( $param:expr ),*) => {
-        //         $(
-        //             $f($arg1, $arg2, $param) |
-        //         )* 0
-        //     }
-        // }
-        // let moves = do_multiple!(self.moves_in_dir(mine, theirs);
-        // -9, -8, -7, -1, 1, 7, 8, 9);
-        let mut moves = BitBoard::new();
-        for dir in bitboard::SHIFT_DIRS {
-            moves |= moves_in_dir(mine, theirs, *dir);
+    pub fn pieces(&self, colour: Colour) -> BitBoard {
+        match colour {
+            Colour::Black => self.blacks,
+            Colour::White => self.whites,
         }
-        let moves = moves & !mine & !theirs;
-        Moves(player, moves)
     }
 
-    fn apply(&self, mov: Move) -> Self {
-        let (mut mine, mut theirs) = match mov.player {
+    pub(crate) fn mine_and_theirs(&self, player: Colour) -> (BitBoard, BitBoard) {
+        match player {
             Colour::Black => (self.blacks, self.whites),
             Colour::White => (self.whites, self.blacks)
-        };
+        }
+    }
 
+    /**
+     * The board after the given move, which flips the given pieces.
+     */
+    pub(crate) fn with_flips(&self, mov: Move, flips: BitBoard) -> Self {
+        let (mut mine, mut theirs) = self.mine_and_theirs(mov.player);
         let mov_bb = BitBoard::from((mov.row, mov.col));
 
-        let mut flips = BitBoard::new();
-        for dir in SHIFT_DIRS {
-            let span1 = dumb7fill(mine, theirs, dir.shift());
-            let span2 = dumb7fill(mov_bb, theirs, dir.reverse().shift());
-
-            flips |= span1 & span2;
-        }
-
         mine |= mov_bb | flips;
         theirs &= !flips;
 
+        let mut key = self.key ^ zobrist::square_key(mov.player, mov.row, mov.col);
+        let mut remaining_flips = flips;
+        while !remaining_flips.is_empty() {
+            let square = remaining_flips.pop_next_bit().to_square();
+            key ^= zobrist::flip_key_at(square);
+        }
+
         if mov.player == Colour::Black {
             BitBoardBoard {
                 blacks: mine,
                 whites: theirs,
+                key,
             }
         } else {
             BitBoardBoard {
                 blacks: theirs,
                 whites: mine,
+                key,
             }
         }
     }
+}
+
+impl<const N: usize> Board for BitBoardBoard<N> {
+    type MoveSet = Moves;
+
+    const SIZE: Pos = {
+        assert!(!Self::SQUARES.is_empty());
+        N as Pos
+    };
+
+    fn is_valid_move(&self, mov: Move) -> bool {
+        let all_moves_bb = self.moves(mov.player).1;
+        let mov_bb = BitBoard::from((mov.row, mov.col));
+        !(all_moves_bb & mov_bb).is_empty()
+    }
+
+    fn moves(&self, player: Colour) -> Self::MoveSet {
+        let (mine, theirs) = self.mine_and_theirs(player);
+        let moves = movegen::moves(mine, theirs);
+        if N == 8 {
+            Moves(player, moves)
+        } else {
+            Moves(player, moves & Self::SQUARES)
+        }
+    }
+
+    fn apply(&self, mov: Move) -> Self {
+        let (mine, theirs) = self.mine_and_theirs(mov.player);
+        let flips = movegen::flips(mine, theirs, BitBoard::from((mov.row, mov.col)));
+        self.with_flips(mov, flips)
+    }
 
     fn get(&self, row: Pos, col: Pos) -> Option<Colour> {
         let b = self.blacks.bit(row, col);
@@ -85,6 +131,13 @@ impl Board for BitBoardBoard {
     }
 
     fn set(&mut self, row: Pos, col: Pos, value: Option<Colour>) {
+        if let Some(old) = self.get(row, col) {
+            self.key ^= zobrist::square_key(old, row, col);
+        }
+        if let Some(new) = value {
+            self.key ^= zobrist::square_key(new, row, col);
+        }
+
         let bit = BitBoard::from((row, col));
         self.blacks &= !bit;
         self.whites &= !bit;
@@ -98,6 +151,37 @@ impl Board for BitBoardBoard {
     fn scores(&self) -> (Score, Score) {
         (self.blacks.count() as Score, self.whites.count() as Score)
     }
+
+    fn zobrist_key(&self) -> u64 {
+        self.key
+    }
+
+    fn transform(&self, transform: Transform) -> Self {
+        if N != 8 {
+            return transform_board(self, transform);
+        }
+
+        let blacks = self.blacks.transform(transform);
+        let whites = self.whites.transform(transform);
+        let mut key = 0;
+        for (colour, bb) in [(Colour::Black, blacks), (Colour::White, whites)] {
+            let mut remaining = bb;
+            while !remaining.is_empty() {
+                key ^= zobrist::square_key_at(colour, remaining.pop_next_bit().to_square());
+            }
+        }
+        BitBoardBoard { blacks, whites, key }
+    }
+
+    fn canonical(&self) -> (Self, Transform) {
+        canonical_by_key(self, |b| (b.blacks.bits(), b.whites.bits()))
+    }
+}
+
+impl<const N: usize> Hash for BitBoardBoard<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.key);
+    }
 }
 
 #[derive(Debug)]
@@ -129,7 +213,7 @@ mod test {
 
     #[test]
     fn test_new() {
-        let bb = BitBoardBoard::new();
+        let bb: BitBoardBoard = BitBoardBoard::new();
 
         assert_eq!(2, bb.blacks.count());
         assert_eq!(2, bb.whites.count());
@@ -137,9 +221,9 @@ mod test {
 
     #[test]
     fn test_new_moves() {
-        let bb = BitBoardBoard::new();
+        let bb: BitBoardBoard = BitBoardBoard::new();
         let moves = bb.moves(Colour::Black);
-        let expected_moves = BitBoard::from(&[(2, 4), (3, 5), (4, 2), (5, 3)]);
+        let expected_moves = BitBoard::from(&[(2, 3), (3, 2), (4, 5), (5, 4)]);
         assert_eq!(expected_moves, moves.1);
         let all_moves: Vec<_> = moves.collect();
         assert_eq!(4, all_moves.len());
@@ -147,7 +231,7 @@ mod test {
 
     #[test]
     fn test_apply_move() {
-        let bb = BitBoardBoard::new();
+        let bb: BitBoardBoard = BitBoardBoard::new();
         for mov in bb.moves(Colour::Black) {
             let bb2 = bb.apply(mov);
             assert_eq!((4, 1), bb2.scores());
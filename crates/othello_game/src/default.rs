@@ -1,19 +1,34 @@
-use crate::{Board, BOARD_SIZE, Colour, Move, Pos, Score};
+use std::hash::{Hash, Hasher};
+
+use crate::{Board, Colour, Move, Pos, Score, zobrist, MAX_BOARD_SIZE};
 use crate::direction::{DIRECTIONS, IterateFrom};
 
-#[derive(Clone, Default, PartialEq)]
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
 pub(crate) struct Square {
     pub(crate) piece: Option<Colour>
 }
 
-#[derive(Clone, Default, PartialEq)]
-pub struct DefaultBoard {
-    squares: [[Square; BOARD_SIZE as usize]; BOARD_SIZE as usize]
+/**
+ * A simple board of N x N squares.  Any even size from 4 up to `MAX_BOARD_SIZE` can be used.
+ */
+#[derive(Clone, Eq, PartialEq)]
+pub struct DefaultBoard<const N: usize = 8> {
+    squares: [[Square; N]; N],
+    key: u64,
 }
 
-impl DefaultBoard {
+impl<const N: usize> Default for DefaultBoard<N> {
+    fn default() -> Self {
+        DefaultBoard {
+            squares: [[Square::default(); N]; N],
+            key: 0,
+        }
+    }
+}
+
+impl<const N: usize> DefaultBoard<N> {
     fn flip(&mut self, player: Colour, row: Pos, col: Pos, dy: Pos, dx: Pos) {
-        let mut iter = (dy, dx).iterate_from(row, col);
+        let mut iter = (dy, dx).iterate_from(row, col, Self::SIZE);
 
         loop {
             let Some((r, c)) = iter.next() else { return; };
@@ -22,7 +37,7 @@ impl DefaultBoard {
             if colour == player { break; }
         }
 
-        let mut iter = (dy, dx).iterate_from(row, col);
+        let mut iter = (dy, dx).iterate_from(row, col, Self::SIZE);
         loop {
             let Some((r, c)) = iter.next() else { return; };
             let Some(colour) = self.squares[r as usize][c as usize].piece else { return; };
@@ -30,13 +45,14 @@ impl DefaultBoard {
             if colour == player { break; }
 
             self.squares[r as usize][c as usize].piece = Some(player);
+            self.key ^= zobrist::square_key(player, r, c) ^ zobrist::square_key(colour, r, c);
         }
     }
 
     #[inline(always)]
     fn count_in_dir(&self, player: Colour, row: Pos, col: Pos, dy: Pos, dx: Pos) -> usize {
         let mut count = 0;
-        let mut iter = (dy, dx).iterate_from(row, col);
+        let mut iter = (dy, dx).iterate_from(row, col, Self::SIZE);
 
         loop {
             let Some((r, c)) = iter.next() else { return 0; };
@@ -50,9 +66,14 @@ impl DefaultBoard {
     }
 }
 
-impl Board for DefaultBoard {
+impl<const N: usize> Board for DefaultBoard<N> {
     type MoveSet = Vec<Move>;
 
+    const SIZE: Pos = {
+        assert!(N >= 4 && N <= MAX_BOARD_SIZE as usize && N.is_multiple_of(2), "unsupported board size");
+        N as Pos
+    };
+
     #[inline(always)]
     fn is_valid_move(&self, mov: Move) -> bool {
         //if mov.player != self.next_turn { return false; }
@@ -65,7 +86,7 @@ impl Board for DefaultBoard {
     }
 
     fn moves(&self, for_player: Colour) -> Self::MoveSet {
-        (0..8).flat_map(|i| (0..8)
+        (0..Self::SIZE).flat_map(|i| (0..Self::SIZE)
             .map(move |j| Move { player: for_player, row: i, col: j}))
             .filter(|mov| self.is_valid_move(*mov)).collect()
     }
@@ -78,6 +99,7 @@ impl Board for DefaultBoard {
             .for_each(|(dy, dx)| newboard.flip(mov.player, mov.row, mov.col, *dy, *dx));
 
         newboard.squares[mov.row as usize][mov.col as usize].piece = Some(mov.player);
+        newboard.key ^= zobrist::square_key(mov.player, mov.row, mov.col);
 
         newboard
     }
@@ -87,7 +109,14 @@ impl Board for DefaultBoard {
     }
 
     fn set(&mut self, row: Pos, col: Pos, value: Option<Colour>) {
-        self.squares[row as usize][col as usize].piece = value;
+        let square = &mut self.squares[row as usize][col as usize];
+        if let Some(old) = square.piece {
+            self.key ^= zobrist::square_key(old, row, col);
+        }
+        if let Some(new) = value {
+            self.key ^= zobrist::square_key(new, row, col);
+        }
+        square.piece = value;
     }
 
     fn scores(&self) -> (Score, Score) {
@@ -99,4 +128,14 @@ impl Board for DefaultBoard {
             .for_each(|c| if c == Colour::Black { black_count += 1; } else { white_count += 1; });
         (black_count, white_count)
     }
+
+    fn zobrist_key(&self) -> u64 {
+        self.key
+    }
+}
+
+impl<const N: usize> Hash for DefaultBoard<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.key);
+    }
 }
@@ -15,7 +15,8 @@ pub(crate) struct DirectionIterator {
     dx: Pos,
     dy: Pos,
     row: Pos,
-    col: Pos
+    col: Pos,
+    size: Pos
 }
 
 impl Iterator for DirectionIterator {
@@ -24,18 +25,18 @@ impl Iterator for DirectionIterator {
     fn next(&mut self) -> Option<Self::Item> {
         self.row += self.dy;
         self.col += self.dx;
-        if out_of_range(self.row, self.col) { return None; }
+        if out_of_range(self.row, self.col, self.size) { return None; }
         Some((self.row, self.col))
     }
 }
 
 pub(crate) trait IterateFrom {
-    fn iterate_from(&self, row: Pos, col: Pos) -> DirectionIterator;
+    fn iterate_from(&self, row: Pos, col: Pos, size: Pos) -> DirectionIterator;
 }
 
 impl IterateFrom for (Pos, Pos) {
 
-    fn iterate_from(&self, row: Pos, col: Pos) -> DirectionIterator {
-        DirectionIterator { dx: self.0, dy: self.1, row, col }
+    fn iterate_from(&self, row: Pos, col: Pos, size: Pos) -> DirectionIterator {
+        DirectionIterator { dx: self.0, dy: self.1, row, col, size }
     }
 }
@@ -0,0 +1,452 @@
+/*!
+ * Reading and writing games in GGF, the Generic Game Format used by internet Othello servers
+ * such as GGS.
+ *
+ * A game is a list of tags between "(;" and ";)", each a name followed by a value in square
+ * brackets:
+ *
+ * ```text
+ * (;GM[Othello]PC[GGS/os]DT[2003.12.15_13:24:03.MST]PB[Saio]PW[Zebra]RB[2197.01]RW[2199.72]
+ * TI[15:00//02:00]TY[8]RE[+4.000]BO[8 -------- -------- -------- ---O*--- ---*O--- -------- -------- -------- *]
+ * B[f5//0.01]W[d6/-2.50/1.20]B[c3]...;)
+ * ```
+ *
+ * The board is given row by row with "*" for Black, "O" for White and "-" for empty, followed by
+ * the player to move.  Each move may be followed by an evaluation and the time taken to play it.
+ */
+
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter, Write};
+
+use crate::{Board, Colour, GameRepr, Move, Pos};
+use crate::default::DefaultBoard;
+use crate::notation::{play_recorded_move, TranscriptError, TranscriptErrorKind};
+
+/**
+ * How a game ended, from the suffix of its result.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GgfEnding {
+    Normal,
+    Resigned,
+    Timeout,
+    Agreed,
+}
+
+/**
+ * The result of a game: the disc difference for Black, and how the game ended.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GgfResult {
+    pub score: f64,
+    pub ending: GgfEnding,
+}
+
+/**
+ * The header of a game.  Tags that aren't recognised are kept in `other`, in the order they were
+ * found.
+ */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GgfHeader {
+    pub place: Option<String>,
+    pub date: Option<String>,
+    pub black: Option<String>,
+    pub white: Option<String>,
+    pub black_rating: Option<f64>,
+    pub white_rating: Option<f64>,
+    pub time_control: Option<String>,
+    pub game_type: Option<String>,
+    pub result: Option<GgfResult>,
+    pub other: Vec<(String, String)>,
+}
+
+/**
+ * A move, along with the player's evaluation of it and the time it took in seconds, if recorded.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GgfMove {
+    pub mov: Move,
+    pub eval: Option<f64>,
+    pub time: Option<f64>,
+}
+
+/**
+ * A game read from GGF.  Every move has been checked to be legal, and passes that the record left
+ * out have been added.
+ */
+#[derive(Clone)]
+pub struct GgfGame<B: Board = DefaultBoard> {
+    pub header: GgfHeader,
+    pub start: GameRepr<B>,
+    pub moves: Vec<GgfMove>,
+}
+
+impl<B: Board> GgfGame<B> {
+    /**
+     * The position after all the moves have been played.
+     */
+    pub fn position(&self) -> GameRepr<B> {
+        self.moves.iter().fold(self.start.clone(), |game, mov| game.apply(mov.mov))
+    }
+
+    /**
+     * Every move played, including passes.
+     */
+    pub fn all_moves(&self) -> Vec<Move> {
+        self.moves.iter().map(|mov| mov.mov).collect()
+    }
+}
+
+impl<B: Board> Debug for GgfGame<B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GgfGame")
+            .field("header", &self.header)
+            .field("start", &self.start)
+            .field("moves", &self.moves)
+            .finish()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GgfErrorKind {
+    MissingStart,
+    Unterminated,
+    InvalidTag(String),
+    UnsupportedGame(String),
+    MissingBoard,
+    InvalidBoard(String),
+    WrongBoardSize { expected: Pos, found: String },
+    InvalidValue { tag: String, value: String },
+    Move(TranscriptError),
+}
+
+/**
+ * An error reading a GGF file, identifying the failed game by its position in the file (starting
+ * from 1).
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct GgfError {
+    pub game_number: usize,
+    pub kind: GgfErrorKind,
+}
+
+impl Display for GgfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "game {}: ", self.game_number)?;
+        match &self.kind {
+            GgfErrorKind::MissingStart => write!(f, "expected \"(;\""),
+            GgfErrorKind::Unterminated => write!(f, "game is not terminated by \";)\""),
+            GgfErrorKind::InvalidTag(text) => write!(f, "invalid tag at \"{text}\""),
+            GgfErrorKind::UnsupportedGame(game) => write!(f, "unsupported game \"{game}\""),
+            GgfErrorKind::MissingBoard => write!(f, "no starting board"),
+            GgfErrorKind::InvalidBoard(board) => write!(f, "invalid board \"{board}\""),
+            GgfErrorKind::WrongBoardSize { expected, found } =>
+                write!(f, "expected a board of size {expected}, found \"{found}\""),
+            GgfErrorKind::InvalidValue { tag, value } => write!(f, "invalid value \"{value}\" for {tag}"),
+            GgfErrorKind::Move(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for GgfError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            GgfErrorKind::Move(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * Split the body of a game into its tags, unescaping "\]" and "\\" in the values.
+ */
+fn parse_tags(body: &str) -> Result<Vec<(&str, String)>, GgfErrorKind> {
+    let mut tags = Vec::new();
+    let mut rest = body.trim_start();
+    while !rest.is_empty() {
+        let name_len = rest.find(|ch: char| !ch.is_ascii_alphanumeric()).unwrap_or(rest.len());
+        let (name, after_name) = rest.split_at(name_len);
+        let invalid = || GgfErrorKind::InvalidTag(rest.chars().take(20).collect());
+        let Some(after_bracket) = after_name.strip_prefix('[') else { return Err(invalid()) };
+        if name.is_empty() {
+            return Err(invalid());
+        }
+
+        let mut value = String::new();
+        let mut chars = after_bracket.char_indices();
+        let end = loop {
+            match chars.next() {
+                Some((_, '\\')) => value.extend(chars.next().map(|(_, ch)| ch)),
+                Some((i, ']')) => break i,
+                Some((_, ch)) => value.push(ch),
+                None => return Err(GgfErrorKind::Unterminated),
+            }
+        };
+
+        tags.push((name, value));
+        rest = after_bracket[end + 1..].trim_start();
+    }
+    Ok(tags)
+}
+
+fn parse_number(tag: &str, value: &str) -> Result<f64, GgfErrorKind> {
+    value.trim().parse()
+        .map_err(|_| GgfErrorKind::InvalidValue { tag: tag.to_string(), value: value.to_string() })
+}
+
+/**
+ * Parse a time such as "12.5", "01:30" or "1:02:03" into seconds.
+ */
+fn parse_time(tag: &str, value: &str) -> Result<f64, GgfErrorKind> {
+    value.split(':').try_fold(0.0, |total, part| Ok(total * 60.0 + parse_number(tag, part)?))
+}
+
+fn parse_result(value: &str) -> Result<GgfResult, GgfErrorKind> {
+    let (score, ending) = value.split_once(':').unwrap_or((value, ""));
+    let ending = match ending {
+        "" => GgfEnding::Normal,
+        "r" => GgfEnding::Resigned,
+        "t" => GgfEnding::Timeout,
+        "s" => GgfEnding::Agreed,
+        _ => return Err(GgfErrorKind::InvalidValue { tag: "RE".to_string(), value: value.to_string() }),
+    };
+    let score = if score.is_empty() { 0.0 } else { parse_number("RE", score)? };
+    Ok(GgfResult { score, ending })
+}
+
+fn parse_board<B: Board>(value: &str) -> Result<GameRepr<B>, GgfErrorKind> {
+    let invalid = || GgfErrorKind::InvalidBoard(value.to_string());
+    let mut tokens: Vec<&str> = value.split_whitespace().collect();
+    let size = tokens.first().copied().ok_or_else(invalid)?;
+    if size.parse() != Ok(B::SIZE) {
+        return Err(GgfErrorKind::WrongBoardSize { expected: B::SIZE, found: size.to_string() });
+    }
+    if tokens.len() < 3 {
+        return Err(invalid());
+    }
+    let side = tokens.pop().ok_or_else(invalid)?;
+
+    let squares: String = tokens[1..].concat();
+    if squares.chars().count() != (B::SIZE as usize) * (B::SIZE as usize) {
+        return Err(invalid());
+    }
+
+    let mut game: GameRepr<B> = GameRepr::empty();
+    for (i, ch) in squares.chars().enumerate() {
+        let piece = match ch {
+            '*' => Some(Colour::Black),
+            'O' => Some(Colour::White),
+            '-' => None,
+            _ => return Err(invalid()),
+        };
+        game.board.set(i as Pos / B::SIZE, i as Pos % B::SIZE, piece);
+    }
+    game.next_turn = match side {
+        "*" => Colour::Black,
+        "O" => Colour::White,
+        _ => return Err(invalid()),
+    };
+    Ok(game)
+}
+
+fn parse_body<B: Board>(body: &str) -> Result<GgfGame<B>, GgfErrorKind> {
+    let tags = parse_tags(body)?;
+    let mut header = GgfHeader::default();
+
+    let start = match tags.iter().find(|(name, _)| *name == "BO") {
+        Some((_, board)) => parse_board(board)?,
+        None => return Err(GgfErrorKind::MissingBoard),
+    };
+
+    let mut game = start.clone();
+    let mut played = Vec::new();
+    let mut moves = Vec::new();
+
+    for (name, value) in tags {
+        match name {
+            "GM" if !value.eq_ignore_ascii_case("othello") => return Err(GgfErrorKind::UnsupportedGame(value)),
+            "GM" | "BO" => (),
+            "PC" => header.place = Some(value),
+            "DT" => header.date = Some(value),
+            "PB" => header.black = Some(value),
+            "PW" => header.white = Some(value),
+            "RB" => header.black_rating = Some(parse_number(name, &value)?),
+            "RW" => header.white_rating = Some(parse_number(name, &value)?),
+            "TI" => header.time_control = Some(value),
+            "TY" => header.game_type = Some(value),
+            "RE" => header.result = Some(parse_result(&value)?),
+            "B" | "W" => {
+                let player = if name == "B" { Colour::Black } else { Colour::White };
+                let move_error = |kind| GgfErrorKind::Move(TranscriptError {
+                    move_number: moves.len() + 1,
+                    text: value.clone(),
+                    kind,
+                });
+
+                /* Moves are written as "d3", "d3/-2.5/1.20" or "d3//1.20" */
+                let mut parts = value.split('/');
+                let mov = Move::parse(parts.next().unwrap_or(""), player)
+                    .map_err(|err| move_error(TranscriptErrorKind::InvalidMove(err)))?;
+                let mut optional_part = || parts.next().filter(|part| !part.trim().is_empty());
+                let eval = optional_part().map(|part| parse_number(name, part)).transpose()?;
+                let time = optional_part().map(|part| parse_time(name, part)).transpose()?;
+
+                let before = played.len();
+                play_recorded_move(&mut game, &mut played, mov, Some(player)).map_err(move_error)?;
+
+                /* A pass may have been added before the move */
+                for pass in &played[before..played.len() - 1] {
+                    moves.push(GgfMove { mov: *pass, eval: None, time: None });
+                }
+                moves.push(GgfMove { mov: played[played.len() - 1], eval, time });
+            }
+            _ => header.other.push((name.to_string(), value)),
+        }
+    }
+
+    Ok(GgfGame { header, start, moves })
+}
+
+/**
+ * The games in a GGF file, read one at a time.
+ */
+pub struct GgfGames<'a, B: Board> {
+    rest: &'a str,
+    game_number: usize,
+    board: std::marker::PhantomData<B>,
+}
+
+impl<B: Board> Iterator for GgfGames<'_, B> {
+    type Item = Result<GgfGame<B>, GgfError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.rest.trim_start();
+        if rest.is_empty() {
+            return None;
+        }
+
+        self.game_number += 1;
+        let error = |kind| GgfError { game_number: self.game_number, kind };
+
+        let Some(body) = rest.strip_prefix("(;") else {
+            self.rest = "";
+            return Some(Err(error(GgfErrorKind::MissingStart)));
+        };
+        let Some(end) = find_end(body) else {
+            self.rest = "";
+            return Some(Err(error(GgfErrorKind::Unterminated)));
+        };
+
+        self.rest = &body[end + 2..];
+        Some(parse_body(&body[..end]).map_err(error))
+    }
+}
+
+/**
+ * Find the ";)" that ends a game, skipping over tag values.
+ */
+fn find_end(body: &str) -> Option<usize> {
+    let mut in_value = false;
+    let mut chars = body.char_indices();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\\' if in_value => { chars.next(); }
+            '[' => in_value = true,
+            ']' => in_value = false,
+            ';' if !in_value && body[i + 1..].starts_with(')') => return Some(i),
+            _ => (),
+        }
+    }
+    None
+}
+
+/**
+ * Read the games in a GGF file one at a time, so that large archives needn't be parsed all at
+ * once.
+ */
+pub fn ggf_games<B: Board>(text: &str) -> GgfGames<'_, B> {
+    GgfGames { rest: text, game_number: 0, board: std::marker::PhantomData }
+}
+
+/**
+ * Parse every game in a GGF file.
+ */
+pub fn parse_ggf<B: Board>(text: &str) -> Result<Vec<GgfGame<B>>, GgfError> {
+    ggf_games(text).collect()
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(']', "\\]")
+}
+
+/**
+ * Write a game as GGF.
+ */
+pub fn write_ggf<B: Board>(game: &GgfGame<B>) -> String {
+    let header = &game.header;
+    let mut text = String::from("(;GM[Othello]");
+
+    let mut tag = |name: &str, value: &Option<String>| {
+        if let Some(value) = value {
+            write!(text, "{name}[{}]", escape(value)).unwrap();
+        }
+    };
+    tag("PC", &header.place);
+    tag("DT", &header.date);
+    tag("PB", &header.black);
+    tag("PW", &header.white);
+    tag("RB", &header.black_rating.map(|rating| format!("{rating:.2}")));
+    tag("RW", &header.white_rating.map(|rating| format!("{rating:.2}")));
+    tag("TI", &header.time_control);
+    tag("TY", &header.game_type);
+    tag("RE", &header.result.map(|result| {
+        let ending = match result.ending {
+            GgfEnding::Normal => "",
+            GgfEnding::Resigned => ":r",
+            GgfEnding::Timeout => ":t",
+            GgfEnding::Agreed => ":s",
+        };
+        format!("{:+.3}{ending}", result.score)
+    }));
+    for (name, value) in &header.other {
+        tag(name, &Some(value.clone()));
+    }
+
+    write!(text, "BO[{}", B::SIZE).unwrap();
+    for row in 0..B::SIZE {
+        text.push(' ');
+        for col in 0..B::SIZE {
+            text.push(match game.start.board.get(row, col) {
+                Some(Colour::Black) => '*',
+                Some(Colour::White) => 'O',
+                None => '-',
+            });
+        }
+    }
+    text.push_str(match game.start.next_turn {
+        Colour::Black => " *]",
+        Colour::White => " O]",
+    });
+
+    for mov in &game.moves {
+        let name = match mov.mov.player {
+            Colour::Black => "B",
+            Colour::White => "W",
+        };
+        let square = if mov.mov.is_pass() { "pa".to_string() } else { mov.mov.to_string().to_ascii_lowercase() };
+        write!(text, "{name}[{square}").unwrap();
+        match (mov.eval, mov.time) {
+            (None, None) => (),
+            (eval, time) => {
+                text.push('/');
+                if let Some(eval) = eval { write!(text, "{eval:.2}").unwrap(); }
+                text.push('/');
+                if let Some(time) = time { write!(text, "{time:.2}").unwrap(); }
+            }
+        }
+        text.push(']');
+    }
+
+    text.push_str(";)");
+    text
+}
@@ -0,0 +1,143 @@
+use crate::{Board, Colour, Game, GameRepr, Move, Pos, Score};
+use crate::default::DefaultBoard;
+
+/**
+ * A game that remembers every move (and pass) played, so that moves can be undone and redone.
+ *
+ * The position after each move is kept, so stepping to any ply is cheap.  Playing a new move after
+ * undoing discards the moves that could have been redone.
+ */
+#[derive(Clone)]
+pub struct GameHistory<B: Board=DefaultBoard> {
+    positions: Vec<GameRepr<B>>,
+    moves: Vec<Move>,
+    ply: usize,
+}
+
+impl<B: Board> GameHistory<B> {
+    pub fn new() -> Self {
+        Self::from_position(GameRepr::new())
+    }
+
+    pub fn from_position(start: GameRepr<B>) -> Self {
+        Self {
+            positions: vec![start],
+            moves: Vec::new(),
+            ply: 0,
+        }
+    }
+
+    pub fn start(&self) -> &GameRepr<B> {
+        &self.positions[0]
+    }
+
+    pub fn current(&self) -> &GameRepr<B> {
+        &self.positions[self.ply]
+    }
+
+    /**
+     * The number of moves played to reach the current position.
+     */
+    pub fn ply(&self) -> usize {
+        self.ply
+    }
+
+    /**
+     * The moves played to reach the current position.
+     */
+    pub fn moves(&self) -> &[Move] {
+        &self.moves[..self.ply]
+    }
+
+    /**
+     * All recorded moves, including any that have been undone but can still be redone.
+     */
+    pub fn all_moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    pub fn play(&mut self, mov: Move) {
+        let next = self.current().apply(mov);
+        self.positions.truncate(self.ply + 1);
+        self.moves.truncate(self.ply);
+        self.positions.push(next);
+        self.moves.push(mov);
+        self.ply += 1;
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.ply > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.ply < self.moves.len()
+    }
+
+    /**
+     * Step back one move, returning the move that was undone.
+     */
+    pub fn undo(&mut self) -> Option<Move> {
+        if !self.can_undo() { return None }
+        self.ply -= 1;
+        Some(self.moves[self.ply])
+    }
+
+    /**
+     * Step forward one previously undone move, returning that move.
+     */
+    pub fn redo(&mut self) -> Option<Move> {
+        if !self.can_redo() { return None }
+        self.ply += 1;
+        Some(self.moves[self.ply - 1])
+    }
+
+    /**
+     * Jump to the position after the given number of moves.  Returns false, leaving the current
+     * position unchanged, if that many moves haven't been recorded.
+     */
+    pub fn go_to(&mut self, ply: usize) -> bool {
+        if ply > self.moves.len() { return false }
+        self.ply = ply;
+        true
+    }
+}
+
+impl<B: Board> Default for GameHistory<B> {
+    fn default() -> Self {
+        GameHistory::new()
+    }
+}
+
+impl<B: Board> Game for GameHistory<B> {
+    fn next_turn(&self) -> Colour {
+        self.current().next_turn
+    }
+
+    fn is_valid_move(&self, mov: Move) -> bool {
+        self.current().is_valid_move(mov)
+    }
+
+    fn valid_moves(&self, for_player: Colour) -> Vec<Move> {
+        self.current().valid_moves(for_player)
+    }
+
+    fn apply_in_place(&mut self, mov: Move) {
+        self.play(mov);
+    }
+
+    fn get_piece(&self, row: Pos, col: Pos) -> Option<Colour> {
+        self.current().get_piece(row, col)
+    }
+
+    fn scores(&self) -> (Score, Score) {
+        self.current().scores()
+    }
+
+    fn is_game_over(&self) -> bool {
+        self.current().is_game_over()
+    }
+
+    fn must_pass(&self) -> bool {
+        self.current().must_pass()
+    }
+}
@@ -1,36 +1,69 @@
-mod bitboard;
+pub mod bitboard;
 pub mod bitboardgame;
 pub mod default;
+pub mod ggf;
 mod direction;
+pub mod history;
+pub mod movegen;
+pub mod notation;
+pub mod obf;
+pub mod opening;
+pub mod packed;
+mod parse;
+pub mod perft;
+pub mod record;
+#[cfg(feature = "serde")]
+mod serialize;
+pub mod share;
+#[cfg(feature = "svg")]
+pub mod svg;
+pub mod symmetry;
+pub mod tableboard;
+pub mod wthor;
+pub mod zobrist;
 
 use std::fmt::{Debug, Display, Formatter, Write};
+use std::hash::{Hash, Hasher};
 
 use rand::seq::SliceRandom;
 
 use crate::default::DefaultBoard;
-use crate::GameParseError::{InvalidPiece, TooManyColumns, TooManyRows};
+use crate::symmetry::Transform;
 
 pub type Score = i32;
 
 pub type Pos = i8;
 
-const BOARD_SIZE: Pos = 8;
+/**
+ * The largest board supported.  Boards are square, with an even size of at least 4.
+ */
+pub const MAX_BOARD_SIZE: Pos = 10;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "lowercase"))]
 pub enum Colour {
     Black,
     White
 }
 
-pub trait Board: Default {
+pub trait Board: Clone + Default {
     type MoveSet: IntoIterator<Item=Move>;
 
+    /**
+     * The number of rows and columns on the board.
+     */
+    const SIZE: Pos;
+
+    /**
+     * The starting position, with four pieces in the centre of the board.
+     */
     fn new() -> Self {
+        let centre = Self::SIZE / 2;
         let mut board = Self::default();
-        board.set(3, 3, Some(Colour::Black));
-        board.set(3, 4, Some(Colour::White));
-        board.set(4, 3, Some(Colour::White));
-        board.set(4, 4, Some(Colour::Black));
+        board.set(centre - 1, centre - 1, Some(Colour::White));
+        board.set(centre - 1, centre, Some(Colour::Black));
+        board.set(centre, centre - 1, Some(Colour::Black));
+        board.set(centre, centre, Some(Colour::White));
         board
     }
 
@@ -40,6 +73,35 @@ pub trait Board: Default {
     fn get(&self, row: Pos, col: Pos) -> Option<Colour>;
     fn set(&mut self, row: Pos, col: Pos, value: Option<Colour>);
     fn scores(&self) -> (Score, Score);
+
+    /**
+     * The Zobrist key of the pieces on the board; see the `zobrist` module.  Implementations
+     * should keep this up to date as the board changes, rather than recomputing it.
+     */
+    fn zobrist_key(&self) -> u64 {
+        let mut key = 0;
+        for i in 0..Self::SIZE {
+            for j in 0..Self::SIZE {
+                if let Some(colour) = self.get(i, j) {
+                    key ^= zobrist::square_key(colour, i, j);
+                }
+            }
+        }
+        key
+    }
+
+    fn transform(&self, transform: Transform) -> Self {
+        symmetry::transform_board(self, transform)
+    }
+
+    /**
+     * The canonical form of this board under the eight symmetries, along with the transform that
+     * produces it from this board.  A move on the canonical board can be mapped back to this one
+     * with `transform.inverse().apply_move(mov, Self::SIZE)`.
+     */
+    fn canonical(&self) -> (Self, Transform) {
+        symmetry::canonical_by_key(self, symmetry::board_order_key)
+    }
 }
 
 pub trait Game {
@@ -49,6 +111,22 @@ pub trait Game {
     fn apply_in_place(&mut self, mov: Move);
     fn get_piece(&self, row: Pos, col: Pos) -> Option<Colour>;
     fn scores(&self) -> (Score, Score);
+
+    /**
+     * The game is over when neither player has a move available.
+     */
+    fn is_game_over(&self) -> bool {
+        self.valid_moves(Colour::Black).is_empty() && self.valid_moves(Colour::White).is_empty()
+    }
+
+    /**
+     * True if the player to move has no moves available, but the game is not yet over.  That
+     * player must pass, handing the turn back to their opponent.
+     */
+    fn must_pass(&self) -> bool {
+        self.valid_moves(self.next_turn()).is_empty()
+            && !self.valid_moves(self.next_turn().opponent()).is_empty()
+    }
 }
 
 #[derive(Clone, PartialEq)]
@@ -67,23 +145,54 @@ impl<B: Board> GameRepr<B> {
     }
 
     pub fn new() -> Self {
-        let mut board: B = Default::default();
-        board.set(3, 3, Some(Colour::Black));
-        board.set(3, 4, Some(Colour::White));
-        board.set(4, 3, Some(Colour::White));
-        board.set(4, 4, Some(Colour::Black));
         Self {
             next_turn: Colour::Black,
-            board,
+            board: B::new(),
         }
     }
 
     pub fn apply(&self, mov: Move) -> Self {
+        if mov.is_pass() {
+            return self.pass();
+        }
         Self {
             board: self.board.apply(mov),
             next_turn: self.next_turn.opponent(),
         }
     }
+
+    pub fn pass(&self) -> Self {
+        Self {
+            board: self.board.clone(),
+            next_turn: self.next_turn.opponent(),
+        }
+    }
+
+    /**
+     * The Zobrist key of the position, including the player to move.
+     */
+    pub fn zobrist_key(&self) -> u64 {
+        match self.next_turn {
+            Colour::Black => self.board.zobrist_key(),
+            Colour::White => self.board.zobrist_key() ^ zobrist::SIDE_KEY,
+        }
+    }
+
+    pub fn transform(&self, transform: Transform) -> Self {
+        Self {
+            board: self.board.transform(transform),
+            next_turn: self.next_turn,
+        }
+    }
+
+    pub fn canonical(&self) -> (Self, Transform) {
+        let (board, transform) = self.board.canonical();
+        (Self { board, next_turn: self.next_turn }, transform)
+    }
+
+    fn has_moves(&self, player: Colour) -> bool {
+        self.board.moves(player).into_iter().next().is_some()
+    }
 }
 
 impl<B: Board> Game for GameRepr<B> {
@@ -92,6 +201,12 @@ impl<B: Board> Game for GameRepr<B> {
     }
 
     fn is_valid_move(&self, mov: Move) -> bool {
+        if mov.is_pass() {
+            return mov.player == self.next_turn && self.must_pass();
+        }
+        if out_of_range(mov.row, mov.col, B::SIZE) {
+            return false;
+        }
         self.board.is_valid_move(mov)
     }
 
@@ -100,10 +215,7 @@ impl<B: Board> Game for GameRepr<B> {
     }
 
     fn apply_in_place(&mut self, mov: Move) {
-        let new_g = Self {
-            board: self.board.apply(mov),
-            next_turn: self.next_turn.opponent(),
-        };
+        let new_g = self.apply(mov);
         *self = new_g;
     }
 
@@ -114,29 +226,60 @@ impl<B: Board> Game for GameRepr<B> {
     fn scores(&self) -> (Score, Score) {
         self.board.scores()
     }
+
+    fn is_game_over(&self) -> bool {
+        !self.has_moves(Colour::Black) && !self.has_moves(Colour::White)
+    }
+
+    fn must_pass(&self) -> bool {
+        !self.has_moves(self.next_turn) && self.has_moves(self.next_turn.opponent())
+    }
+}
+
+impl<B: Board + Eq> Eq for GameRepr<B> {}
+
+impl<B: Board> Hash for GameRepr<B> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.zobrist_key());
+    }
 }
 
 pub type DefaultGame = GameRepr<DefaultBoard>;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct Move {
     pub player: Colour,
     pub row: Pos,
     pub col: Pos
 }
 
+impl Move {
+    /**
+     * A pass, made when the player has no other move available.  It is applied to a game like
+     * any other move, but only hands the turn to the opponent.
+     */
+    pub fn pass(player: Colour) -> Self {
+        Move { player, row: -1, col: -1 }
+    }
+
+    pub fn is_pass(&self) -> bool {
+        self.row < 0
+    }
+}
+
 impl Display for Move {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let colname = self.col as u8 + 65;
-        let rowname = self.row as u8 + 49;
+        if self.is_pass() {
+            return f.write_str("PA");
+        }
+        let colname = self.col as u8 + b'A';
         f.write_char(colname as char)?;
-        f.write_char(rowname as char)?;
-        Ok(())
+        write!(f, "{}", self.row + 1)
     }
 }
 
-fn out_of_range(row: Pos, col: Pos) -> bool {
-    (row | col) as u8 & 0b11111000 != 0
+fn out_of_range(row: Pos, col: Pos, size: Pos) -> bool {
+    row < 0 || col < 0 || row >= size || col >= size
 }
 
 impl Colour {
@@ -163,8 +306,8 @@ impl<B: Board> Default for GameRepr<B> {
 
 impl<B: Board> Debug for GameRepr<B> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for i in 0..8 {
-            for j in 0..8 {
+        for i in 0..B::SIZE {
+            for j in 0..B::SIZE {
                 let piece = self.board.get(i, j);
                 f.write_str(match piece {
                     Some(Colour::Black) => "○",
@@ -178,42 +321,33 @@ impl<B: Board> Debug for GameRepr<B> {
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub enum GameParseError {
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GameParseErrorKind {
     TooManyRows,
     TooManyColumns,
-    InvalidPiece,
+    InvalidPiece(char),
+    WrongRowNumber { expected: usize, found: String },
 }
 
-impl<B: Board> TryFrom<&str> for GameRepr<B> {
-    type Error = GameParseError;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut game: GameRepr<B> = GameRepr::empty();
-
-        for (i, line) in value.split_terminator('\n').enumerate() {
-            for (j, ch) in line.chars().enumerate() {
-                let piece = match ch {
-                    '○' => Some(Colour::Black),
-                    '●' => Some(Colour::White),
-                    '·' => None,
-                    _ => return Err(InvalidPiece)
-                };
-
-                if i >= 8 { return Err(TooManyRows); }
-                if j >= 8 { return Err(TooManyColumns); }
-                game.board.set(i as Pos, j as Pos, piece);
-            }
-        }
-
-        Ok(game)
-    }
+/**
+ * An error parsing a board diagram, with the line and column where it was found (both starting
+ * from 1).
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: GameParseErrorKind,
 }
 
+/**
+ * Copy a board to another board type of the same size.
+ */
 pub fn convert_board<B: Board, B2: Board>(board: &B) -> B2 {
+    assert_eq!(B::SIZE, B2::SIZE, "boards must be the same size");
     let mut new_board: B2 = B2::default();
-    for i in 0..8 {
-        for j in 0..8 {
+    for i in 0..B::SIZE {
+        for j in 0..B::SIZE {
             let piece = board.get(i, j);
             new_board.set(i, j, piece);
         }
@@ -222,16 +356,16 @@ pub fn convert_board<B: Board, B2: Board>(board: &B) -> B2 {
 }
 
 pub fn convert<B: Board>(game: &dyn Game) -> GameRepr<B> {
-    let mut b = DefaultBoard::new();
-    for i in 0..8 {
-        for j in 0..8 {
+    let mut board = B::default();
+    for i in 0..B::SIZE {
+        for j in 0..B::SIZE {
             let piece = game.get_piece(i, j);
-            b.set(i, j, piece);
+            board.set(i, j, piece);
         }
     }
     GameRepr {
         next_turn: game.next_turn(),
-        board: convert_board(&b)
+        board
     }
 }
 
@@ -239,8 +373,8 @@ pub fn random_board<B: Board>() -> B {
     const PIECE_CHOICES: [Option<Colour>; 3] = [None, Some(Colour::Black), Some(Colour::White)];
 
     let mut board = B::default();
-    for i in 0..8 {
-        for j in 0..8 {
+    for i in 0..B::SIZE {
+        for j in 0..B::SIZE {
             let random_piece = PIECE_CHOICES.choose(&mut rand::thread_rng()).unwrap();
             board.set(i, j, *random_piece);
         }
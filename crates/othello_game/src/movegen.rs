@@ -0,0 +1,371 @@
+/*!
+ * Move generation and flip calculation on bitboards.
+ *
+ * There are several implementations (backends) of the fills these need.  The simplest runs a
+ * dumb7fill in each of the eight directions in turn.  The Kogge-Stone backends use parallel
+ * prefix fills (https://www.chessprogramming.org/Kogge-Stone_Algorithm), which need only three
+ * steps per direction instead of six, and run four directions at once: those that shift bits
+ * left (towards higher squares) and then those that shift bits right.  On x86-64 CPUs with AVX2
+ * the four directions are held in a single vector register.
+ */
+
+use std::sync::OnceLock;
+
+use crate::bitboard::{dumb7fill, dumb7fill_occluded, BitBoard, SHIFT_DIRS};
+
+const NOT_COL_0: u64 = 0xFEFEFEFEFEFEFEFE;
+const NOT_COL_7: u64 = 0x7F7F7F7F7F7F7F7F;
+
+/**
+ * The four directions handled in each pass, as shift amounts: Right, DownLeft, Down and
+ * DownRight when shifting left, and Left, UpRight, Up and UpLeft when shifting right.
+ */
+const LANE_SHIFTS: [u64; 4] = [1, 7, 8, 9];
+
+/**
+ * Masks to stop bits wrapping around from one edge of the board to the other, for each direction
+ * in `LANE_SHIFTS`.
+ */
+const LEFT_MASKS: [u64; 4] = [NOT_COL_0, NOT_COL_7, !0, NOT_COL_0];
+const RIGHT_MASKS: [u64; 4] = [NOT_COL_7, NOT_COL_0, !0, NOT_COL_7];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FillBackend {
+    Dumb7Fill,
+    KoggeStone,
+    KoggeStoneAvx2,
+}
+
+impl FillBackend {
+    pub const ALL: [FillBackend; 3] = [FillBackend::Dumb7Fill, FillBackend::KoggeStone, FillBackend::KoggeStoneAvx2];
+
+    /**
+     * Whether this backend can run on the current CPU.
+     */
+    pub fn is_available(self) -> bool {
+        match self {
+            FillBackend::KoggeStoneAvx2 => avx2::is_available(),
+            _ => true,
+        }
+    }
+
+    /**
+     * The fastest backend available on the current CPU.
+     */
+    pub fn best() -> FillBackend {
+        if avx2::is_available() { FillBackend::KoggeStoneAvx2 } else { FillBackend::KoggeStone }
+    }
+
+    /**
+     * The empty squares where a player with pieces `mine` can move, against an opponent with
+     * pieces `theirs`.
+     */
+    pub fn moves(self, mine: BitBoard, theirs: BitBoard) -> BitBoard {
+        BitBoard::from_bits((self.functions().moves)(mine.bits(), theirs.bits()))
+    }
+
+    /**
+     * The opponent's pieces flipped when a player with pieces `mine` moves on square `mov`.
+     */
+    pub fn flips(self, mine: BitBoard, theirs: BitBoard, mov: BitBoard) -> BitBoard {
+        BitBoard::from_bits((self.functions().flips)(mine.bits(), theirs.bits(), mov.bits()))
+    }
+
+    fn functions(self) -> Functions {
+        match self {
+            FillBackend::Dumb7Fill => Functions { moves: moves_dumb7fill, flips: flips_dumb7fill },
+            FillBackend::KoggeStone => Functions { moves: moves_kogge_stone, flips: flips_kogge_stone },
+            FillBackend::KoggeStoneAvx2 => {
+                assert!(avx2::is_available(), "AVX2 is not available on this CPU");
+                Functions { moves: avx2::moves, flips: avx2::flips }
+            }
+        }
+    }
+}
+
+/**
+ * A backend's implementations of `moves` and `flips`.
+ */
+#[derive(Clone, Copy)]
+struct Functions {
+    moves: fn(u64, u64) -> u64,
+    flips: fn(u64, u64, u64) -> u64,
+}
+
+/* The best backend is chosen the first time it is needed, rather than on every call */
+static BEST_FUNCTIONS: OnceLock<Functions> = OnceLock::new();
+
+#[inline]
+fn best_functions() -> Functions {
+    *BEST_FUNCTIONS.get_or_init(|| FillBackend::best().functions())
+}
+
+/**
+ * Find moves using the best backend for this CPU.
+ */
+#[inline]
+pub fn moves(mine: BitBoard, theirs: BitBoard) -> BitBoard {
+    BitBoard::from_bits((best_functions().moves)(mine.bits(), theirs.bits()))
+}
+
+/**
+ * Find flips using the best backend for this CPU.
+ */
+#[inline]
+pub fn flips(mine: BitBoard, theirs: BitBoard, mov: BitBoard) -> BitBoard {
+    BitBoard::from_bits((best_functions().flips)(mine.bits(), theirs.bits(), mov.bits()))
+}
+
+fn moves_dumb7fill(mine: u64, theirs: u64) -> u64 {
+    let (mine, theirs) = (BitBoard::from_bits(mine), BitBoard::from_bits(theirs));
+    let mut moves = BitBoard::new();
+    for dir in SHIFT_DIRS {
+        moves |= dumb7fill_occluded(mine, theirs, *dir).shift(*dir);
+    }
+    (moves & !mine & !theirs).bits()
+}
+
+fn flips_dumb7fill(mine: u64, theirs: u64, mov: u64) -> u64 {
+    let (mine, theirs, mov) = (BitBoard::from_bits(mine), BitBoard::from_bits(theirs), BitBoard::from_bits(mov));
+    let mut flips = BitBoard::new();
+    for dir in SHIFT_DIRS {
+        let span1 = dumb7fill(mine, theirs, *dir);
+        let span2 = dumb7fill(mov, theirs, dir.reverse());
+
+        flips |= span1 & span2;
+    }
+    flips.bits()
+}
+
+/**
+ * Fill from `gen` through `pro` in the four directions that shift left, one in each lane.  The
+ * result includes the original `gen` squares.
+ */
+#[inline(always)]
+fn fill_left(gen: u64, pro: u64) -> [u64; 4] {
+    let mut result = [0; 4];
+    for i in 0..4 {
+        let shift = LANE_SHIFTS[i];
+        let mut gen = gen;
+        let mut pro = pro & LEFT_MASKS[i];
+        gen |= pro & (gen << shift);
+        pro &= pro << shift;
+        gen |= pro & (gen << (2 * shift));
+        pro &= pro << (2 * shift);
+        gen |= pro & (gen << (4 * shift));
+        result[i] = gen;
+    }
+    result
+}
+
+#[inline(always)]
+fn fill_right(gen: u64, pro: u64) -> [u64; 4] {
+    let mut result = [0; 4];
+    for i in 0..4 {
+        let shift = LANE_SHIFTS[i];
+        let mut gen = gen;
+        let mut pro = pro & RIGHT_MASKS[i];
+        gen |= pro & (gen >> shift);
+        pro &= pro >> shift;
+        gen |= pro & (gen >> (2 * shift));
+        pro &= pro >> (2 * shift);
+        gen |= pro & (gen >> (4 * shift));
+        result[i] = gen;
+    }
+    result
+}
+
+fn moves_kogge_stone(mine: u64, theirs: u64) -> u64 {
+    let left = fill_left(mine, theirs);
+    let right = fill_right(mine, theirs);
+
+    let mut moves = 0;
+    for i in 0..4 {
+        let shift = LANE_SHIFTS[i];
+        moves |= ((left[i] & theirs) << shift) & LEFT_MASKS[i];
+        moves |= ((right[i] & theirs) >> shift) & RIGHT_MASKS[i];
+    }
+    moves & !(mine | theirs)
+}
+
+fn flips_kogge_stone(mine: u64, theirs: u64, mov: u64) -> u64 {
+    let left = fill_left(mov, theirs);
+    let right = fill_right(mov, theirs);
+
+    /* A line of the opponent's pieces is flipped only if it ends with one of ours */
+    let mut flips = 0;
+    for i in 0..4 {
+        let shift = LANE_SHIFTS[i];
+        if ((left[i] << shift) & LEFT_MASKS[i] & mine) != 0 {
+            flips |= left[i] & theirs;
+        }
+        if ((right[i] >> shift) & RIGHT_MASKS[i] & mine) != 0 {
+            flips |= right[i] & theirs;
+        }
+    }
+    flips
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use std::arch::x86_64::*;
+
+    use super::{LANE_SHIFTS, LEFT_MASKS, RIGHT_MASKS};
+
+    #[inline]
+    pub(super) fn is_available() -> bool {
+        is_x86_feature_detected!("avx2")
+    }
+
+    /* Only called through `FillBackend::functions`, which checks that AVX2 is available */
+    pub(super) fn moves(mine: u64, theirs: u64) -> u64 {
+        // SAFETY: the CPU supports AVX2, as checked by the caller.
+        unsafe { moves_avx2(mine, theirs) }
+    }
+
+    pub(super) fn flips(mine: u64, theirs: u64, mov: u64) -> u64 {
+        // SAFETY: the CPU supports AVX2, as checked by the caller.
+        unsafe { flips_avx2(mine, theirs, mov) }
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    fn lanes(values: [u64; 4]) -> __m256i {
+        _mm256_set_epi64x(values[3] as i64, values[2] as i64, values[1] as i64, values[0] as i64)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    fn splat(value: u64) -> __m256i {
+        _mm256_set1_epi64x(value as i64)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    fn fill_left(gen: __m256i, pro: __m256i) -> __m256i {
+        let shift1 = lanes(LANE_SHIFTS);
+        let shift2 = _mm256_add_epi64(shift1, shift1);
+        let shift4 = _mm256_add_epi64(shift2, shift2);
+        let mut gen = gen;
+        let mut pro = _mm256_and_si256(pro, lanes(LEFT_MASKS));
+        gen = _mm256_or_si256(gen, _mm256_and_si256(pro, _mm256_sllv_epi64(gen, shift1)));
+        pro = _mm256_and_si256(pro, _mm256_sllv_epi64(pro, shift1));
+        gen = _mm256_or_si256(gen, _mm256_and_si256(pro, _mm256_sllv_epi64(gen, shift2)));
+        pro = _mm256_and_si256(pro, _mm256_sllv_epi64(pro, shift2));
+        _mm256_or_si256(gen, _mm256_and_si256(pro, _mm256_sllv_epi64(gen, shift4)))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    fn fill_right(gen: __m256i, pro: __m256i) -> __m256i {
+        let shift1 = lanes(LANE_SHIFTS);
+        let shift2 = _mm256_add_epi64(shift1, shift1);
+        let shift4 = _mm256_add_epi64(shift2, shift2);
+        let mut gen = gen;
+        let mut pro = _mm256_and_si256(pro, lanes(RIGHT_MASKS));
+        gen = _mm256_or_si256(gen, _mm256_and_si256(pro, _mm256_srlv_epi64(gen, shift1)));
+        pro = _mm256_and_si256(pro, _mm256_srlv_epi64(pro, shift1));
+        gen = _mm256_or_si256(gen, _mm256_and_si256(pro, _mm256_srlv_epi64(gen, shift2)));
+        pro = _mm256_and_si256(pro, _mm256_srlv_epi64(pro, shift2));
+        _mm256_or_si256(gen, _mm256_and_si256(pro, _mm256_srlv_epi64(gen, shift4)))
+    }
+
+    /**
+     * OR together the four lanes of a vector.
+     */
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    fn or_lanes(v: __m256i) -> u64 {
+        let halves = _mm_or_si128(_mm256_castsi256_si128(v), _mm256_extracti128_si256::<1>(v));
+        (_mm_cvtsi128_si64(halves) | _mm_extract_epi64::<1>(halves)) as u64
+    }
+
+    #[target_feature(enable = "avx2")]
+    fn moves_avx2(mine: u64, theirs: u64) -> u64 {
+        let shifts = lanes(LANE_SHIFTS);
+        let theirs_v = splat(theirs);
+
+        let left = fill_left(splat(mine), theirs_v);
+        let left = _mm256_sllv_epi64(_mm256_and_si256(left, theirs_v), shifts);
+        let left = _mm256_and_si256(left, lanes(LEFT_MASKS));
+
+        let right = fill_right(splat(mine), theirs_v);
+        let right = _mm256_srlv_epi64(_mm256_and_si256(right, theirs_v), shifts);
+        let right = _mm256_and_si256(right, lanes(RIGHT_MASKS));
+
+        or_lanes(_mm256_or_si256(left, right)) & !(mine | theirs)
+    }
+
+    #[target_feature(enable = "avx2")]
+    fn flips_avx2(mine: u64, theirs: u64, mov: u64) -> u64 {
+        let shifts = lanes(LANE_SHIFTS);
+        let mine_v = splat(mine);
+        let theirs_v = splat(theirs);
+        let zero = _mm256_setzero_si256();
+
+        /* Keep each lane's line of the opponent's pieces only if it ends with one of ours */
+        let left = fill_left(splat(mov), theirs_v);
+        let left_end = _mm256_and_si256(_mm256_sllv_epi64(left, shifts), _mm256_and_si256(lanes(LEFT_MASKS), mine_v));
+        let left_unbounded = _mm256_cmpeq_epi64(left_end, zero);
+        let left = _mm256_andnot_si256(left_unbounded, _mm256_and_si256(left, theirs_v));
+
+        let right = fill_right(splat(mov), theirs_v);
+        let right_end = _mm256_and_si256(_mm256_srlv_epi64(right, shifts), _mm256_and_si256(lanes(RIGHT_MASKS), mine_v));
+        let right_unbounded = _mm256_cmpeq_epi64(right_end, zero);
+        let right = _mm256_andnot_si256(right_unbounded, _mm256_and_si256(right, theirs_v));
+
+        or_lanes(_mm256_or_si256(left, right))
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+mod avx2 {
+    pub(super) fn is_available() -> bool {
+        false
+    }
+
+    pub(super) fn moves(_mine: u64, _theirs: u64) -> u64 {
+        panic!("AVX2 is not available on this architecture");
+    }
+
+    pub(super) fn flips(_mine: u64, _theirs: u64, _mov: u64) -> u64 {
+        panic!("AVX2 is not available on this architecture");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bitboard::BitBoard;
+
+    #[test]
+    fn test_backends_agree() {
+        let mut state = 0x1234_5678_9ABC_DEF0u64;
+        let mut random = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        for _ in 0..10000 {
+            let occupied = random() | random();
+            let mine = BitBoard::from_bits(occupied & random());
+            let theirs = BitBoard::from_bits(occupied & !mine.bits());
+
+            let expected_moves = FillBackend::Dumb7Fill.moves(mine, theirs);
+            for backend in FillBackend::ALL.into_iter().filter(|b| b.is_available()) {
+                assert_eq!(expected_moves, backend.moves(mine, theirs), "{backend:?}");
+            }
+
+            for mov in expected_moves {
+                let mov = BitBoard::from(mov);
+                let expected_flips = FillBackend::Dumb7Fill.flips(mine, theirs, mov);
+                assert!(!expected_flips.is_empty());
+                for backend in FillBackend::ALL.into_iter().filter(|b| b.is_available()) {
+                    assert_eq!(expected_flips, backend.flips(mine, theirs, mov), "{backend:?}");
+                }
+            }
+        }
+    }
+}
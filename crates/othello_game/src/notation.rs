@@ -0,0 +1,210 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::{Board, Colour, Game, GameRepr, Move, Pos, MAX_BOARD_SIZE};
+
+const PASS_TOKENS: &[&str] = &["pa", "ps", "--", "pass"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MoveParseError {
+    Empty,
+    InvalidColumn(char),
+    InvalidRow(char),
+    TrailingCharacters(String),
+}
+
+impl Display for MoveParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoveParseError::Empty => write!(f, "no move given"),
+            MoveParseError::InvalidColumn(ch) => write!(f, "invalid column '{ch}'"),
+            MoveParseError::InvalidRow(ch) => write!(f, "invalid row '{ch}'"),
+            MoveParseError::TrailingCharacters(rest) => write!(f, "unexpected \"{rest}\" after move"),
+        }
+    }
+}
+
+impl Error for MoveParseError {}
+
+impl Move {
+    /**
+     * Parse a move such as "c4" or "C4" for the given player.  Rows beyond the ninth have two
+     * digits, as in "a10".  Any of "PA", "PS", "--" or "pass" is accepted as a pass.  This takes
+     * the place of `FromStr`, which has no way to be told whose move it is.
+     */
+    pub fn parse(text: &str, player: Colour) -> Result<Move, MoveParseError> {
+        let text = text.trim();
+        if PASS_TOKENS.iter().any(|tok| text.eq_ignore_ascii_case(tok)) {
+            return Ok(Move::pass(player));
+        }
+
+        let mut chars = text.chars();
+        let col_ch = chars.next().ok_or(MoveParseError::Empty)?;
+        let row_ch = chars.next().ok_or(MoveParseError::Empty)?;
+        let row_text = &text[col_ch.len_utf8()..];
+        let digits = row_text.bytes().take(2).take_while(u8::is_ascii_digit).count();
+        let (row_text, rest) = row_text.split_at(if digits > 0 { digits } else { row_ch.len_utf8() });
+        if !rest.is_empty() {
+            return Err(MoveParseError::TrailingCharacters(rest.to_string()));
+        }
+
+        let col = match col_ch.to_ascii_lowercase() {
+            c @ 'a'..='z' if ((c as u8 - b'a') as Pos) < MAX_BOARD_SIZE => (c as u8 - b'a') as Pos,
+            _ => return Err(MoveParseError::InvalidColumn(col_ch)),
+        };
+        let row = match row_text.parse::<Pos>() {
+            Ok(d) if (1..=MAX_BOARD_SIZE).contains(&d) && !row_text.starts_with('0') => d - 1,
+            _ => return Err(MoveParseError::InvalidRow(row_ch)),
+        };
+
+        Ok(Move { player, row, col })
+    }
+}
+
+/**
+ * Why a move read from a transcript or game record couldn't be played: either its text isn't a
+ * move, the move isn't legal in the position reached, or the game had already finished.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum TranscriptErrorKind {
+    InvalidMove(MoveParseError),
+    IllegalMove,
+    GameOver,
+}
+
+/**
+ * An error in a transcript, identifying the failed move by its position in the transcript
+ * (starting from 1) and its text.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct TranscriptError {
+    pub move_number: usize,
+    pub text: String,
+    pub kind: TranscriptErrorKind,
+}
+
+impl Display for TranscriptError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "move {} \"{}\": ", self.move_number, self.text)?;
+        match &self.kind {
+            TranscriptErrorKind::InvalidMove(err) => write!(f, "{err}"),
+            TranscriptErrorKind::IllegalMove => write!(f, "illegal move"),
+            TranscriptErrorKind::GameOver => write!(f, "game is already over"),
+        }
+    }
+}
+
+impl Error for TranscriptError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            TranscriptErrorKind::InvalidMove(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * Split a transcript into move tokens.  Moves may be run together ("f5d6c3") or separated by
+ * whitespace or commas.  A move takes all the digits after its column, so "a10" is one move.
+ */
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    loop {
+        rest = rest.trim_start_matches(|ch: char| ch.is_whitespace() || ch == ',');
+        if rest.is_empty() { break }
+
+        let digits = rest.get(1..).map_or(0, |tail| tail.bytes().take_while(u8::is_ascii_digit).count());
+        let len = if rest.get(..4).is_some_and(|tok| tok.eq_ignore_ascii_case("pass")) {
+            4
+        } else if rest.starts_with(|ch: char| ch.is_ascii_alphabetic()) && digits > 0 {
+            1 + digits
+        } else {
+            rest.char_indices().nth(2).map_or(rest.len(), |(i, _)| i)
+        };
+        let (token, remaining) = rest.split_at(len);
+        tokens.push(token);
+        rest = remaining;
+    }
+    tokens
+}
+
+/**
+ * Replay a transcript of moves onto a game, checking that each is legal.  Passes may be given
+ * explicitly, or left out in which case they are inserted when the player to move has no moves.
+ * Returns the final game and every move played, including passes.
+ */
+pub fn replay_transcript<B: Board>(game: &GameRepr<B>, text: &str) -> Result<(GameRepr<B>, Vec<Move>), TranscriptError> {
+    let mut game = game.clone();
+    let mut moves = Vec::new();
+
+    for (i, token) in tokenize(text).into_iter().enumerate() {
+        let error = |kind| TranscriptError { move_number: i + 1, text: token.to_string(), kind };
+
+        if game.is_game_over() {
+            return Err(error(TranscriptErrorKind::GameOver));
+        }
+        let mov = Move::parse(token, game.next_turn)
+            .map_err(|err| error(TranscriptErrorKind::InvalidMove(err)))?;
+        play_recorded_move(&mut game, &mut moves, mov, None).map_err(error)?;
+    }
+
+    Ok((game, moves))
+}
+
+/**
+ * Play a move read from a game record, first inserting a pass if the player to move has no moves
+ * but the move isn't a pass.  The move is checked against `player` if the record says whose move
+ * it is; otherwise it is played by the player to move.
+ */
+pub(crate) fn play_recorded_move<B: Board>(game: &mut GameRepr<B>, moves: &mut Vec<Move>, mov: Move, player: Option<Colour>) -> Result<(), TranscriptErrorKind> {
+    if game.is_game_over() {
+        return Err(TranscriptErrorKind::GameOver);
+    }
+
+    if !mov.is_pass() && game.must_pass() {
+        let pass = Move::pass(game.next_turn);
+        *game = game.apply(pass);
+        moves.push(pass);
+    }
+
+    if player.is_some_and(|player| player != game.next_turn) {
+        return Err(TranscriptErrorKind::IllegalMove);
+    }
+    let mov = Move { player: game.next_turn, ..mov };
+    if !game.is_valid_move(mov) {
+        return Err(TranscriptErrorKind::IllegalMove);
+    }
+    *game = game.apply(mov);
+    moves.push(mov);
+    Ok(())
+}
+
+/**
+ * Replay a transcript from the standard starting position.
+ */
+pub fn parse_transcript<B: Board>(text: &str) -> Result<(GameRepr<B>, Vec<Move>), TranscriptError> {
+    replay_transcript(&GameRepr::new(), text)
+}
+
+/**
+ * Write a list of moves as a compact transcript, such as "f5d6c3".  Passes are written as "PA".
+ */
+pub fn write_transcript(moves: &[Move]) -> String {
+    moves.iter()
+        .map(|mov| if mov.is_pass() { mov.to_string() } else { mov.to_string().to_ascii_lowercase() })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(vec!["f5", "d6", "c3"], tokenize("f5d6c3"));
+        assert_eq!(vec!["F5", "d6", "PA", "c3"], tokenize(" F5, d6 PA\nc3 "));
+        assert_eq!(vec!["f5", "pass", "d6", "x"], tokenize("f5 pass d6x"));
+        assert_eq!(vec!["a10", "j9", "e5"], tokenize("a10j9e5"));
+    }
+}
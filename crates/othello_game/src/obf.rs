@@ -0,0 +1,252 @@
+/*!
+ * The one-line position format used by most Othello programs and test suites, such as the FFO
+ * endgame positions and Edax's OBF files.
+ *
+ * A position is written as one character per square, row by row from A1: "X" for Black, "O" for
+ * White and "-" for empty.  Then comes the player to move, "X" or "O", and an optional list of
+ * moves, each with its score and ended by a semicolon.  The score is required, so a bare "F5;" is
+ * an error:
+ *
+ * ```text
+ * ---------------------------OX------XO--------------------------- X; F5:+0; D3:+0;
+ * ```
+ */
+
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+use crate::{Board, Colour, GameRepr, Move, Pos, Score};
+use crate::notation::MoveParseError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ObfErrorKind {
+    Empty,
+    WrongLength { expected: usize, found: usize },
+    InvalidPiece(char),
+    InvalidSide(String),
+    InvalidMove(MoveParseError),
+    MissingScore,
+    InvalidScore(String),
+}
+
+/**
+ * An error reading a position, with the line it was found on (starting from 1).
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObfError {
+    pub line_number: usize,
+    pub kind: ObfErrorKind,
+}
+
+impl Display for ObfErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObfErrorKind::Empty => write!(f, "no position given"),
+            ObfErrorKind::WrongLength { expected, found } =>
+                write!(f, "expected {expected} squares, found {found}"),
+            ObfErrorKind::InvalidPiece(ch) => write!(f, "invalid piece '{ch}'"),
+            ObfErrorKind::InvalidSide(side) => write!(f, "invalid side to move \"{side}\""),
+            ObfErrorKind::InvalidMove(err) => write!(f, "{err}"),
+            ObfErrorKind::MissingScore => write!(f, "move has no score"),
+            ObfErrorKind::InvalidScore(score) => write!(f, "invalid score \"{score}\""),
+        }
+    }
+}
+
+impl Display for ObfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line_number, self.kind)
+    }
+}
+
+impl Error for ObfError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ObfErrorKind::InvalidMove(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * A position from a test suite, along with the moves it lists and their scores for the player to
+ * move.  Suites usually give the best move first.
+ */
+#[derive(Clone)]
+pub struct ObfPosition<B: Board> {
+    pub game: GameRepr<B>,
+    pub moves: Vec<(Move, Score)>,
+}
+
+impl<B: Board> ObfPosition<B> {
+    /**
+     * The first listed move and its score.
+     */
+    pub fn best_move(&self) -> Option<(Move, Score)> {
+        self.moves.first().copied()
+    }
+}
+
+impl<B: Board> Debug for ObfPosition<B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObfPosition")
+            .field("game", &self.game)
+            .field("moves", &self.moves)
+            .finish()
+    }
+}
+
+fn parse_piece(ch: char) -> Result<Option<Colour>, ObfErrorKind> {
+    match ch {
+        'X' | 'x' | '*' | 'B' | 'b' => Ok(Some(Colour::Black)),
+        'O' | 'o' | '0' | 'W' | 'w' => Ok(Some(Colour::White)),
+        '-' | '.' | '_' => Ok(None),
+        _ => Err(ObfErrorKind::InvalidPiece(ch)),
+    }
+}
+
+/**
+ * Parse the player to move.  Besides "X" and "O", the "Black to move" wording of the FFO files is
+ * accepted.
+ */
+fn parse_side(text: &str) -> Result<Colour, ObfErrorKind> {
+    let side = text.trim().to_ascii_lowercase();
+    let side = side.strip_suffix("to move").unwrap_or(&side).trim_end();
+    match side {
+        "x" | "*" | "b" | "black" => Ok(Colour::Black),
+        "o" | "0" | "w" | "white" => Ok(Colour::White),
+        _ => Err(ObfErrorKind::InvalidSide(text.trim().to_string())),
+    }
+}
+
+fn parse_annotation(text: &str, player: Colour) -> Result<(Move, Score), ObfErrorKind> {
+    let (mov, score) = text.split_once(':').unwrap_or((text, ""));
+    let mov = Move::parse(mov, player).map_err(ObfErrorKind::InvalidMove)?;
+    if score.trim().is_empty() {
+        return Err(ObfErrorKind::MissingScore);
+    }
+    let score = score.trim();
+    let score = score.strip_prefix('+').unwrap_or(score).parse()
+        .map_err(|_| ObfErrorKind::InvalidScore(score.to_string()))?;
+    Ok((mov, score))
+}
+
+fn parse_line<B: Board>(line: &str) -> Result<ObfPosition<B>, ObfErrorKind> {
+    let mut parts = line.split(';');
+    let position = parts.next().unwrap_or("").trim();
+    if position.is_empty() {
+        return Err(ObfErrorKind::Empty);
+    }
+
+    let board_text: String = position.chars().take_while(|ch| !ch.is_whitespace()).collect();
+    let game = GameRepr {
+        board: parse_board(&board_text)?,
+        next_turn: parse_side(&position[board_text.len()..])?,
+    };
+
+    let moves = parts.map(str::trim)
+        .filter(|annotation| !annotation.is_empty())
+        .map(|annotation| parse_annotation(annotation, game.next_turn))
+        .collect::<Result<_, _>>()?;
+
+    Ok(ObfPosition { game, moves })
+}
+
+/**
+ * Parse just the squares of a board, as in "---...---".
+ */
+pub(crate) fn parse_board<B: Board>(text: &str) -> Result<B, ObfErrorKind> {
+    let num_squares = (B::SIZE as usize) * (B::SIZE as usize);
+    let found = text.chars().count();
+    if found != num_squares {
+        return Err(ObfErrorKind::WrongLength { expected: num_squares, found });
+    }
+
+    let mut board = B::default();
+    for (i, ch) in text.chars().enumerate() {
+        board.set(i as Pos / B::SIZE, i as Pos % B::SIZE, parse_piece(ch)?);
+    }
+    Ok(board)
+}
+
+/**
+ * Parse a single position, ignoring any moves listed after it.
+ */
+pub fn parse_position<B: Board>(line: &str) -> Result<GameRepr<B>, ObfError> {
+    parse_obf(line).map(|position| position.game)
+}
+
+/**
+ * Parse a single position along with its listed moves.
+ */
+pub fn parse_obf<B: Board>(line: &str) -> Result<ObfPosition<B>, ObfError> {
+    parse_line(line).map_err(|kind| ObfError { line_number: 1, kind })
+}
+
+/**
+ * Parse a file of positions, one per line.  Blank lines, and comment lines starting with "%" or
+ * "#", are skipped.  An FFO file, which has the player to move on the line after the board, is
+ * read as a single position.
+ */
+pub fn parse_obf_file<B: Board>(text: &str) -> Result<Vec<ObfPosition<B>>, ObfError> {
+    let lines: Vec<(usize, &str)> = text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('%') && !line.starts_with('#'))
+        .collect();
+
+    if let [(line_number, board), (_, side)] = lines[..] {
+        if parse_side(side).is_ok() {
+            let line = format!("{board} {side}");
+            return parse_line(&line)
+                .map(|position| vec![position])
+                .map_err(|kind| ObfError { line_number, kind });
+        }
+    }
+
+    lines.into_iter()
+        .map(|(line_number, line)| parse_line(line).map_err(|kind| ObfError { line_number, kind }))
+        .collect()
+}
+
+/**
+ * Write a position, such as "---...--- X".
+ */
+pub fn write_position<B: Board>(game: &GameRepr<B>) -> String {
+    let mut text = write_board(&game.board);
+    text.push(' ');
+    text.push(match game.next_turn {
+        Colour::Black => 'X',
+        Colour::White => 'O',
+    });
+    text
+}
+
+/**
+ * Write just the squares of a board, as in "---...---".
+ */
+pub(crate) fn write_board<B: Board>(board: &B) -> String {
+    let mut text = String::with_capacity((B::SIZE as usize) * (B::SIZE as usize) + 2);
+    for row in 0..B::SIZE {
+        for col in 0..B::SIZE {
+            text.push(match board.get(row, col) {
+                Some(Colour::Black) => 'X',
+                Some(Colour::White) => 'O',
+                None => '-',
+            });
+        }
+    }
+    text
+}
+
+/**
+ * Write a position with its listed moves, such as "---...--- X; F5:+2; D3:-4;".
+ */
+pub fn write_obf<B: Board>(position: &ObfPosition<B>) -> String {
+    let mut text = write_position(&position.game);
+    text.push(';');
+    for (mov, score) in &position.moves {
+        text.push_str(&format!(" {mov}:{score:+};"));
+    }
+    text
+}
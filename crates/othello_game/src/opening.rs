@@ -0,0 +1,158 @@
+/*!
+ * Naming the opening of a game.
+ *
+ * The four first moves of a game are equivalent under the symmetries of the starting position, so
+ * openings are written starting with f5, and a game is turned so that its first move is f5 before
+ * it is looked up.  Only 8x8 games are classified.
+ */
+
+use std::sync::OnceLock;
+
+use crate::{Move, Pos};
+use crate::bitboardgame::BitBoardBoard;
+use crate::notation::{parse_transcript, TranscriptError};
+use crate::symmetry::Transform;
+
+/**
+ * The named openings built into the crate, as (name, moves) from the standard starting position.
+ * The names and lines are from Robert Gatliff's list of Othello opening names.
+ */
+pub const OPENINGS: &[(&str, &str)] = &[
+    /* Diagonal openings */
+    ("Diagonal", "f5f6"),
+    ("X-square", "f5f6e6f4g7"),
+    ("Snake / Peasant", "f5f6e6f4g6"),
+    ("Pyramid / Checkerboarding Peasant", "f5f6e6f4g6c5g4g5f3e3"),
+    ("Heath / Tobidashi", "f5f6e6f4g5"),
+    ("Rabbit", "f5f6e6f4e3"),
+    ("Buffalo / Tanida", "f5f6e6f4c3"),
+
+    /* Parallel openings */
+    ("Parallel", "f5f4"),
+
+    /* Perpendicular openings: the Tiger */
+    ("Perpendicular", "f5d6"),
+    ("Tiger", "f5d6c3d3c4"),
+    ("Stephenson", "f5d6c3d3c4f4c5b3c2"),
+    ("Comp'Oth", "f5d6c3d3c4f4c5b3c2e6c6b4b5d2e3a6c1b1"),
+    ("Aircraft / Feldborg", "f5d6c3d3c4f4c5b3c2e3d2c6b4a4"),
+    ("No-Kung", "f5d6c3d3c4f4f6f3e6e7"),
+
+    /* Perpendicular openings: the Cow */
+    ("Cow", "f5d6c5f4e3"),
+    ("Rose", "f5d6c5f4e3c6d3f6e6d7"),
+];
+
+/**
+ * The symmetries that leave the starting position unchanged.
+ */
+const START_SYMMETRIES: [Transform; 4] = [
+    Transform::Identity, Transform::Rotate180, Transform::FlipDiagonal, Transform::FlipAntiDiagonal,
+];
+
+const F5: (Pos, Pos) = (4, 5);
+
+/**
+ * Turn a game so that it starts with f5, returning the squares played.  Passes are kept as (-1, -1).
+ */
+fn normalise(moves: &[Move]) -> Vec<(Pos, Pos)> {
+    let transform = moves.first()
+        .and_then(|first| START_SYMMETRIES.into_iter().find(|t| t.apply(first.row, first.col, 8) == F5))
+        .unwrap_or(Transform::Identity);
+    moves.iter()
+        .map(|mov| transform.apply_move(*mov, 8))
+        .map(|mov| (mov.row, mov.col))
+        .collect()
+}
+
+/**
+ * How a game matches the known openings.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OpeningMatch<'a> {
+    /**
+     * The name of the longest known opening the game starts with, if any.
+     */
+    pub name: Option<&'a str>,
+    /**
+     * The number of moves in that opening.
+     */
+    pub opening_plies: usize,
+    /**
+     * The number of moves from the start that follow some known opening.  If this is less than the
+     * length of the game, the game leaves the known openings with the move after.
+     */
+    pub known_plies: usize,
+}
+
+/**
+ * A table of named openings.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct Openings {
+    lines: Vec<(String, Vec<(Pos, Pos)>)>,
+}
+
+impl Openings {
+    /**
+     * An empty table.
+     */
+    pub fn new() -> Self {
+        Openings { lines: Vec::new() }
+    }
+
+    /**
+     * The table of openings in `OPENINGS`.
+     */
+    pub fn standard() -> Self {
+        let mut openings = Openings::new();
+        for (name, moves) in OPENINGS {
+            openings.add(name, moves).expect("built-in openings are legal");
+        }
+        openings
+    }
+
+    /**
+     * Add an opening, given as a transcript such as "f5d6c3".  It may start with any of the four
+     * first moves.
+     */
+    pub fn add(&mut self, name: &str, moves: &str) -> Result<(), TranscriptError> {
+        let (_, moves) = parse_transcript::<BitBoardBoard>(moves)?;
+        self.lines.push((name.to_string(), normalise(&moves)));
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /**
+     * Find the opening a game starts with.
+     */
+    pub fn classify(&self, moves: &[Move]) -> OpeningMatch<'_> {
+        let squares = normalise(moves);
+        let mut result = OpeningMatch { name: None, opening_plies: 0, known_plies: 0 };
+
+        for (name, line) in &self.lines {
+            let common = line.iter().zip(&squares).take_while(|(a, b)| a == b).count();
+            result.known_plies = result.known_plies.max(common);
+            if common == line.len() && line.len() > result.opening_plies {
+                result.name = Some(name);
+                result.opening_plies = line.len();
+            }
+        }
+        result
+    }
+}
+
+/**
+ * Find the opening a game starts with, using the built-in table.
+ */
+pub fn classify_opening(moves: &[Move]) -> OpeningMatch<'static> {
+    static STANDARD: OnceLock<Openings> = OnceLock::new();
+    STANDARD.get_or_init(Openings::standard).classify(moves)
+}
@@ -0,0 +1,424 @@
+/*!
+ * A compact binary encoding of 8x8 positions, for datasets and opening books with millions of
+ * positions.
+ *
+ * A position packs into 16 bytes: Black's and White's pieces as little-endian u64 bitboards, with
+ * square A1 in bit 0 and H8 in bit 63.  A file of positions starts with an 8 byte header:
+ *
+ * - The magic bytes "OTHP".
+ * - The format version, currently 1.
+ * - A byte of flags saying which labels each position has: bit 0 for a score, bit 1 for a best
+ *   move and bit 2 for the game result.
+ * - Two reserved zero bytes.
+ *
+ * Each position follows as a fixed length record: the 16 bytes of the position, a byte for the
+ * player to move (0 for Black, 1 for White), then one byte for each label in the header:
+ *
+ * - The score for the player to move, as an i8.  -128 means the position has no score.
+ * - The best move, as `8 * row + col`, with 64 for a pass and 255 for no move.
+ * - The final disc difference for the player to move, as an i8.  -128 means no result.
+ */
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Read, Write};
+
+use crate::{Board, Colour, GameRepr, Move, Pos};
+
+pub const MAGIC: [u8; 4] = *b"OTHP";
+pub const VERSION: u8 = 1;
+pub const HEADER_LEN: usize = 8;
+pub const PACKED_LEN: usize = 16;
+
+const HAS_SCORE: u8 = 1;
+const HAS_BEST_MOVE: u8 = 2;
+const HAS_RESULT: u8 = 4;
+
+/* A record with every label: the position, the player to move and three labels */
+const MAX_RECORD_LEN: usize = PACKED_LEN + 4;
+
+const NO_VALUE: i8 = i8::MIN;
+const PASS_SQUARE: u8 = 64;
+const NO_MOVE: u8 = 255;
+
+#[derive(Debug)]
+pub enum PackedError {
+    Io(io::Error),
+    InvalidMagic([u8; 4]),
+    UnsupportedVersion(u8),
+    UnknownFlags(u8),
+    Overlapping { position_number: usize },
+    InvalidSide { position_number: usize, value: u8 },
+    InvalidMove { position_number: usize, value: u8 },
+}
+
+impl Display for PackedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PackedError::Io(err) => write!(f, "{err}"),
+            PackedError::InvalidMagic(magic) => write!(f, "not a position file (starts with {magic:?})"),
+            PackedError::UnsupportedVersion(version) => write!(f, "unsupported version {version}"),
+            PackedError::UnknownFlags(flags) => write!(f, "unknown label flags {flags:#04x}"),
+            PackedError::Overlapping { position_number } =>
+                write!(f, "position {position_number}: a square has pieces of both colours"),
+            PackedError::InvalidSide { position_number, value } =>
+                write!(f, "position {position_number}: invalid side to move {value}"),
+            PackedError::InvalidMove { position_number, value } =>
+                write!(f, "position {position_number}: invalid best move {value}"),
+        }
+    }
+}
+
+impl Error for PackedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PackedError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PackedError {
+    fn from(err: io::Error) -> Self {
+        PackedError::Io(err)
+    }
+}
+
+/**
+ * An 8x8 position as two bitboards and the player to move.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PackedPosition {
+    pub black: u64,
+    pub white: u64,
+    pub next_turn: Colour,
+}
+
+impl PackedPosition {
+    /**
+     * Pack a position.  Panics if the board isn't 8x8.
+     */
+    pub fn from_game<B: Board>(game: &GameRepr<B>) -> PackedPosition {
+        assert_eq!(8, B::SIZE, "only 8x8 positions can be packed");
+        let (mut black, mut white) = (0, 0);
+        for row in 0..8 {
+            for col in 0..8 {
+                let bit = 1 << (row * 8 + col);
+                match game.board.get(row, col) {
+                    Some(Colour::Black) => black |= bit,
+                    Some(Colour::White) => white |= bit,
+                    None => (),
+                }
+            }
+        }
+        PackedPosition { black, white, next_turn: game.next_turn }
+    }
+
+    /**
+     * Unpack a position.  Panics if the board isn't 8x8.
+     */
+    pub fn to_game<B: Board>(&self) -> GameRepr<B> {
+        assert_eq!(8, B::SIZE, "only 8x8 positions can be packed");
+        let mut board = B::default();
+        for square in 0..64 {
+            let piece = if self.black & (1 << square) != 0 {
+                Some(Colour::Black)
+            } else if self.white & (1 << square) != 0 {
+                Some(Colour::White)
+            } else {
+                None
+            };
+            board.set(square as Pos / 8, square as Pos % 8, piece);
+        }
+        GameRepr { board, next_turn: self.next_turn }
+    }
+
+    /**
+     * The 16 bytes of the two bitboards.  The player to move is stored separately.
+     */
+    pub fn to_bytes(&self) -> [u8; PACKED_LEN] {
+        let mut bytes = [0; PACKED_LEN];
+        bytes[..8].copy_from_slice(&self.black.to_le_bytes());
+        bytes[8..].copy_from_slice(&self.white.to_le_bytes());
+        bytes
+    }
+
+    /**
+     * Read the bytes written by `to_bytes`.  Returns None if a square has pieces of both colours.
+     */
+    pub fn from_bytes(bytes: &[u8; PACKED_LEN], next_turn: Colour) -> Option<PackedPosition> {
+        let black = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+        let white = u64::from_le_bytes(bytes[8..].try_into().unwrap());
+        (black & white == 0).then_some(PackedPosition { black, white, next_turn })
+    }
+}
+
+/**
+ * Which labels the positions in a file have.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PositionFileHeader {
+    pub version: u8,
+    pub has_score: bool,
+    pub has_best_move: bool,
+    pub has_result: bool,
+}
+
+impl PositionFileHeader {
+    pub fn new(has_score: bool, has_best_move: bool, has_result: bool) -> Self {
+        PositionFileHeader { version: VERSION, has_score, has_best_move, has_result }
+    }
+
+    /**
+     * The length of each position's record.
+     */
+    pub fn record_len(&self) -> usize {
+        PACKED_LEN + 1 + self.has_score as usize + self.has_best_move as usize + self.has_result as usize
+    }
+
+    pub fn read(reader: &mut impl Read) -> Result<PositionFileHeader, PackedError> {
+        let mut bytes = [0; HEADER_LEN];
+        reader.read_exact(&mut bytes)?;
+
+        let magic = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        if magic != MAGIC {
+            return Err(PackedError::InvalidMagic(magic));
+        }
+        if bytes[4] != VERSION {
+            return Err(PackedError::UnsupportedVersion(bytes[4]));
+        }
+        let flags = bytes[5];
+        if flags & !(HAS_SCORE | HAS_BEST_MOVE | HAS_RESULT) != 0 {
+            return Err(PackedError::UnknownFlags(flags));
+        }
+        Ok(PositionFileHeader {
+            version: bytes[4],
+            has_score: flags & HAS_SCORE != 0,
+            has_best_move: flags & HAS_BEST_MOVE != 0,
+            has_result: flags & HAS_RESULT != 0,
+        })
+    }
+
+    pub fn write(&self, writer: &mut impl Write) -> io::Result<()> {
+        let mut bytes = [0; HEADER_LEN];
+        bytes[..4].copy_from_slice(&MAGIC);
+        bytes[4] = self.version;
+        bytes[5] = if self.has_score { HAS_SCORE } else { 0 }
+            | if self.has_best_move { HAS_BEST_MOVE } else { 0 }
+            | if self.has_result { HAS_RESULT } else { 0 };
+        writer.write_all(&bytes)
+    }
+}
+
+impl Default for PositionFileHeader {
+    fn default() -> Self {
+        PositionFileHeader::new(false, false, false)
+    }
+}
+
+/**
+ * A position with its labels.  Labels the file doesn't have are read as None, and not written.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LabelledPosition {
+    pub position: PackedPosition,
+    /**
+     * The score for the player to move.
+     */
+    pub score: Option<i8>,
+    pub best_move: Option<Move>,
+    /**
+     * The final disc difference of the game, for the player to move.
+     */
+    pub result: Option<i8>,
+}
+
+impl From<PackedPosition> for LabelledPosition {
+    fn from(position: PackedPosition) -> Self {
+        LabelledPosition { position, score: None, best_move: None, result: None }
+    }
+}
+
+fn write_value(value: Option<i8>) -> u8 {
+    value.unwrap_or(NO_VALUE) as u8
+}
+
+fn read_value(byte: u8) -> Option<i8> {
+    Some(byte as i8).filter(|&value| value != NO_VALUE)
+}
+
+/**
+ * The positions in a file, read one at a time.
+ */
+pub struct PositionReader<R: Read> {
+    reader: R,
+    header: PositionFileHeader,
+    positions_read: usize,
+    finished: bool,
+}
+
+impl<R: Read> PositionReader<R> {
+    /**
+     * Read the header of a file, ready to read its positions.
+     */
+    pub fn new(mut reader: R) -> Result<PositionReader<R>, PackedError> {
+        let header = PositionFileHeader::read(&mut reader)?;
+        Ok(PositionReader { reader, header, positions_read: 0, finished: false })
+    }
+
+    pub fn header(&self) -> &PositionFileHeader {
+        &self.header
+    }
+
+    /**
+     * Fill the record, returning false if the file ended cleanly before it.
+     */
+    fn read_record(&mut self, record: &mut [u8]) -> io::Result<bool> {
+        let mut filled = 0;
+        while filled < record.len() {
+            match self.reader.read(&mut record[filled..]) {
+                Ok(0) if filled == 0 => return Ok(false),
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(len) => filled += len,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(true)
+    }
+
+    fn parse_record(&self, record: &[u8]) -> Result<LabelledPosition, PackedError> {
+        let position_number = self.positions_read;
+        let next_turn = match record[PACKED_LEN] {
+            0 => Colour::Black,
+            1 => Colour::White,
+            value => return Err(PackedError::InvalidSide { position_number, value }),
+        };
+        let position = PackedPosition::from_bytes(record[..PACKED_LEN].try_into().unwrap(), next_turn)
+            .ok_or(PackedError::Overlapping { position_number })?;
+
+        let mut labels = record[PACKED_LEN + 1..].iter().copied();
+        let mut next_label = |present: bool| if present { labels.next() } else { None };
+        let score = next_label(self.header.has_score).and_then(read_value);
+        let best_move = match next_label(self.header.has_best_move) {
+            None | Some(NO_MOVE) => None,
+            Some(PASS_SQUARE) => Some(Move::pass(next_turn)),
+            Some(value) if value < PASS_SQUARE =>
+                Some(Move { player: next_turn, row: (value / 8) as Pos, col: (value % 8) as Pos }),
+            Some(value) => return Err(PackedError::InvalidMove { position_number, value }),
+        };
+        let result = next_label(self.header.has_result).and_then(read_value);
+
+        Ok(LabelledPosition { position, score, best_move, result })
+    }
+}
+
+impl<R: Read> Iterator for PositionReader<R> {
+    type Item = Result<LabelledPosition, PackedError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let mut record = [0; MAX_RECORD_LEN];
+        let record = &mut record[..self.header.record_len()];
+        match self.read_record(record) {
+            Ok(true) => {
+                self.positions_read += 1;
+                Some(self.parse_record(record))
+            }
+            Ok(false) => {
+                self.finished = true;
+                None
+            }
+            Err(err) => {
+                /* Don't keep reading after the end of a truncated file */
+                self.finished = true;
+                Some(Err(err.into()))
+            }
+        }
+    }
+}
+
+/**
+ * Writes positions to a file one at a time.
+ */
+pub struct PositionWriter<W: Write> {
+    writer: W,
+    header: PositionFileHeader,
+}
+
+impl<W: Write> PositionWriter<W> {
+    /**
+     * Write the header of a file, ready to write its positions.
+     */
+    pub fn new(mut writer: W, header: PositionFileHeader) -> io::Result<PositionWriter<W>> {
+        header.write(&mut writer)?;
+        Ok(PositionWriter { writer, header })
+    }
+
+    pub fn header(&self) -> &PositionFileHeader {
+        &self.header
+    }
+
+    /**
+     * Write a position, with whichever of its labels the file has.
+     */
+    pub fn write(&mut self, position: &LabelledPosition) -> io::Result<()> {
+        let mut record = [0; MAX_RECORD_LEN];
+        record[..PACKED_LEN].copy_from_slice(&position.position.to_bytes());
+        record[PACKED_LEN] = match position.position.next_turn {
+            Colour::Black => 0,
+            Colour::White => 1,
+        };
+        let mut len = PACKED_LEN + 1;
+        let mut push = |byte| {
+            record[len] = byte;
+            len += 1;
+        };
+        if self.header.has_score {
+            push(write_value(position.score));
+        }
+        if self.header.has_best_move {
+            push(match position.best_move {
+                None => NO_MOVE,
+                Some(mov) if mov.is_pass() => PASS_SQUARE,
+                Some(mov) => (mov.row * 8 + mov.col) as u8,
+            });
+        }
+        if self.header.has_result {
+            push(write_value(position.result));
+        }
+        self.writer.write_all(&record[..len])
+    }
+
+    /**
+     * Flush the file, and return the writer.
+     */
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/**
+ * Read a whole file of positions.
+ */
+pub fn read_positions(reader: impl Read) -> Result<(PositionFileHeader, Vec<LabelledPosition>), PackedError> {
+    let positions = PositionReader::new(reader)?;
+    let header = *positions.header();
+    let positions = positions.collect::<Result<_, _>>()?;
+    Ok((header, positions))
+}
+
+/**
+ * Write a whole file of positions.
+ */
+pub fn write_positions(writer: impl Write, header: PositionFileHeader, positions: &[LabelledPosition]) -> io::Result<()> {
+    let mut writer = PositionWriter::new(writer, header)?;
+    for position in positions {
+        writer.write(position)?;
+    }
+    writer.finish().map(|_| ())
+}
@@ -0,0 +1,146 @@
+/*!
+ * Reading board diagrams, as written by people and other programs.
+ *
+ * A diagram has one line per row, with one character per square.  Any of these dialects may be
+ * used, and mixed:
+ *
+ * - "○" for Black, "●" for White and "·" for empty, as written by `GameRepr`'s `Debug`.
+ * - "X", "O" and "-", as in the `obf` module.
+ * - "x", "o" and ".".
+ *
+ * Spaces between squares are ignored, and a blank line is an empty row.  The board may have a
+ * border of coordinates: lines of column letters, and row numbers at the start or end of each row.
+ * Rows and columns left out are empty.
+ *
+ * The player to move may be given on a line of its own, such as "Black to move", "white" or
+ * "X to move".  A bare "X" or "O" after the last row is also read as the player to move.
+ */
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::{Board, Colour, GameParseError, GameParseErrorKind, GameRepr, Pos};
+
+const COLUMN_LETTERS: &str = "abcdefghij";
+
+impl Display for GameParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameParseErrorKind::TooManyRows => write!(f, "too many rows"),
+            GameParseErrorKind::TooManyColumns => write!(f, "too many columns"),
+            GameParseErrorKind::InvalidPiece(ch) => write!(f, "invalid piece '{ch}'"),
+            GameParseErrorKind::WrongRowNumber { expected, found } =>
+                write!(f, "expected row {expected}, found row \"{found}\""),
+        }
+    }
+}
+
+impl Display for GameParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl Error for GameParseError {}
+
+fn parse_piece(ch: char) -> Option<Option<Colour>> {
+    match ch {
+        '○' | 'X' | 'x' => Some(Some(Colour::Black)),
+        '●' | 'O' | 'o' => Some(Some(Colour::White)),
+        '·' | '-' | '.' => Some(None),
+        _ => None,
+    }
+}
+
+/**
+ * A line of column letters, such as "a b c d e f g h".
+ */
+fn is_column_border(line: &str) -> bool {
+    let letters: String = line.chars().filter(|ch| !ch.is_whitespace()).collect();
+    !letters.is_empty() && COLUMN_LETTERS.starts_with(&letters.to_ascii_lowercase())
+}
+
+/**
+ * A line giving the player to move.  Without "to move", a bare "X" or "O" is only accepted after
+ * the last row, where it can't be a row itself.
+ */
+fn parse_side(line: &str, after_last_row: bool) -> Option<Colour> {
+    let lower = line.to_lowercase();
+    let side = lower.strip_suffix("to move").or_else(|| lower.strip_suffix("to play"));
+    let explicit = side.is_some();
+    match side.unwrap_or(&lower).trim() {
+        "black" => Some(Colour::Black),
+        "white" => Some(Colour::White),
+        "○" if explicit => Some(Colour::Black),
+        "●" if explicit => Some(Colour::White),
+        "x" if explicit || after_last_row => Some(Colour::Black),
+        "o" if explicit || after_last_row => Some(Colour::White),
+        _ => None,
+    }
+}
+
+fn parse_game<B: Board>(text: &str) -> Result<GameRepr<B>, GameParseError> {
+    let size = B::SIZE as usize;
+    let mut game: GameRepr<B> = GameRepr::empty();
+    let mut row = 0;
+
+    for (i, line) in text.lines().enumerate() {
+        let error = |column, kind| GameParseError { line: i + 1, column, kind };
+
+        let trimmed = line.trim();
+        if (trimmed.is_empty() && row >= size) || is_column_border(trimmed) {
+            continue;
+        }
+        if let Some(side) = parse_side(trimmed, row == size) {
+            game.next_turn = side;
+            continue;
+        }
+
+        /* The squares, with their columns in the line, and any row numbers either side */
+        let mut squares: Vec<(usize, char)> = line.chars()
+            .enumerate()
+            .filter(|(_, ch)| !ch.is_whitespace())
+            .map(|(j, ch)| (j + 1, ch))
+            .collect();
+        let labels = [
+            squares.iter().take_while(|(_, ch)| ch.is_ascii_digit()).count(),
+            squares.iter().rev().take_while(|(_, ch)| ch.is_ascii_digit()).count(),
+        ];
+        let trailing: Vec<(usize, char)> = squares.split_off(squares.len() - labels[1].min(squares.len()));
+        let leading: Vec<(usize, char)> = squares.drain(..labels[0].min(squares.len())).collect();
+        for label in [leading, trailing] {
+            let Some(&(column, _)) = label.first() else { continue };
+            let found: String = label.iter().map(|(_, ch)| ch).collect();
+            if found.parse() != Ok(row + 1) {
+                return Err(error(column, GameParseErrorKind::WrongRowNumber { expected: row + 1, found }));
+            }
+        }
+
+        for (j, &(column, ch)) in squares.iter().enumerate() {
+            let piece = parse_piece(ch).ok_or_else(|| error(column, GameParseErrorKind::InvalidPiece(ch)))?;
+            if row >= size { return Err(error(column, GameParseErrorKind::TooManyRows)); }
+            if j >= size { return Err(error(column, GameParseErrorKind::TooManyColumns)); }
+            game.board.set(row as Pos, j as Pos, piece);
+        }
+        row += 1;
+    }
+
+    Ok(game)
+}
+
+impl<B: Board> TryFrom<&str> for GameRepr<B> {
+    type Error = GameParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse_game(value)
+    }
+}
+
+impl<B: Board> FromStr for GameRepr<B> {
+    type Err = GameParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_game(s)
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::thread;
+
+use crate::{Board, Game, GameRepr, Move};
+
+/**
+ * The published perft counts for the standard starting position, for depths 1 onwards.  Passes
+ * count as moves, and a finished game counts as a single leaf however much depth remains.
+ */
+pub const STANDARD_PERFT: &[u64] = &[
+    4,
+    12,
+    56,
+    244,
+    1396,
+    8200,
+    55092,
+    390216,
+    3005288,
+    24571284,
+    212258800,
+    1939886636,
+    18429641748,
+    184042084512,
+];
+
+/**
+ * Count the leaf nodes of the game tree to the given depth, as described at
+ * https://www.chessprogramming.org/Perft.
+ */
+pub fn perft<B: Board>(game: &GameRepr<B>, depth: usize) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let moves = game.board.moves(game.next_turn);
+    if depth == 1 {
+        /* A pass, or the end of the game, is a single leaf */
+        return (moves.into_iter().count() as u64).max(1);
+    }
+
+    let mut count = 0;
+    let mut any_moves = false;
+    for mov in moves {
+        any_moves = true;
+        count += perft(&game.apply(mov), depth - 1);
+    }
+
+    if !any_moves {
+        if game.is_game_over() {
+            return 1;
+        }
+        return perft(&game.pass(), depth - 1);
+    }
+
+    count
+}
+
+fn root_moves<B: Board>(game: &GameRepr<B>) -> Vec<Move> {
+    let moves = game.valid_moves(game.next_turn);
+    if moves.is_empty() && !game.is_game_over() {
+        vec![Move::pass(game.next_turn)]
+    } else {
+        moves
+    }
+}
+
+/**
+ * Count the leaf nodes under each move from this position, to the given depth.
+ */
+pub fn perft_divide<B: Board>(game: &GameRepr<B>, depth: usize) -> Vec<(Move, u64)> {
+    if depth == 0 {
+        return Vec::new();
+    }
+
+    root_moves(game).into_iter()
+        .map(|mov| (mov, perft(&game.apply(mov), depth - 1)))
+        .collect()
+}
+
+/**
+ * Count the leaf nodes to the given depth, sharing the work between threads.  The subtrees two
+ * moves below the root are handed out to threads as they become free.
+ */
+pub fn perft_parallel<B: Board + Send + Sync>(game: &GameRepr<B>, depth: usize, num_threads: usize) -> u64 {
+    if depth <= 2 || num_threads <= 1 || game.is_game_over() {
+        return perft(game, depth);
+    }
+
+    let subtrees: Vec<GameRepr<B>> = root_moves(game).into_iter()
+        .map(|mov| game.apply(mov))
+        .flat_map(|g| root_moves(&g).into_iter().map(move |mov| g.apply(mov)).collect::<Vec<_>>())
+        .collect();
+
+    /* Finished games below the root still count as leaves */
+    let finished: u64 = root_moves(game).into_iter()
+        .filter(|mov| root_moves(&game.apply(*mov)).is_empty())
+        .count() as u64;
+
+    let next_subtree = AtomicUsize::new(0);
+    let total = AtomicU64::new(finished);
+
+    thread::scope(|s| {
+        for _ in 0..num_threads {
+            s.spawn(|| {
+                loop {
+                    let i = next_subtree.fetch_add(1, Ordering::Relaxed);
+                    let Some(subtree) = subtrees.get(i) else { break };
+                    total.fetch_add(perft(subtree, depth - 2), Ordering::Relaxed);
+                }
+            });
+        }
+    });
+
+    total.into_inner()
+}
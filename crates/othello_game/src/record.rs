@@ -0,0 +1,462 @@
+/*!
+ * A text format for complete games, modelled on chess's PGN.
+ *
+ * A record starts with headers, one per line, giving a name and a quoted value.  Then come the
+ * moves, numbered in pairs, each optionally followed by a comment in braces.  A comment may hold an
+ * evaluation and the time taken to think, in seconds; a "}" or backslash in its text is escaped with
+ * a backslash.  The record ends with the result:
+ *
+ * ```text
+ * [Black "AlphaBetaAI"]
+ * [White "RandomAI"]
+ * [BlackAI "max_depth=4 ordering=Heuristic"]
+ * [Result "1-0"]
+ * [Score "40-24"]
+ *
+ * 1. f5 {[%eval +2] [%time 0.15] Best move} d6 2. c3 d3 3. c4 f4
+ * ...
+ * 1-0
+ * ```
+ *
+ * Passes are written as "PA".  A game that doesn't start from the usual position has a "Position"
+ * header, in the format of the `obf` module.
+ */
+
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter, Write};
+
+use crate::{Board, Colour, Game, GameRepr, Move};
+use crate::default::DefaultBoard;
+use crate::notation::{play_recorded_move, TranscriptError, TranscriptErrorKind};
+use crate::obf::{parse_position, write_position, ObfErrorKind};
+
+pub const EVENT: &str = "Event";
+pub const DATE: &str = "Date";
+pub const BLACK: &str = "Black";
+pub const WHITE: &str = "White";
+pub const BLACK_AI: &str = "BlackAI";
+pub const WHITE_AI: &str = "WhiteAI";
+pub const TIME_CONTROL: &str = "TimeControl";
+pub const RESULT: &str = "Result";
+pub const SCORE: &str = "Score";
+pub const POSITION: &str = "Position";
+
+const RESULTS: &[&str] = &["1-0", "0-1", "1/2-1/2", "*"];
+
+const LINE_LENGTH: usize = 80;
+
+/**
+ * A move, with an optional evaluation (from the point of view of the player making it), thinking
+ * time in seconds and comment.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedMove {
+    pub mov: Move,
+    pub eval: Option<f64>,
+    pub time: Option<f64>,
+    pub comment: Option<String>,
+}
+
+impl From<Move> for RecordedMove {
+    fn from(mov: Move) -> Self {
+        RecordedMove { mov, eval: None, time: None, comment: None }
+    }
+}
+
+/**
+ * A complete game record.  Every move has been checked to be legal, and passes that the record
+ * left out have been added.
+ */
+#[derive(Clone)]
+pub struct GameRecord<B: Board = DefaultBoard> {
+    /**
+     * The headers, in the order they are written.
+     */
+    pub headers: Vec<(String, String)>,
+    pub start: GameRepr<B>,
+    pub moves: Vec<RecordedMove>,
+}
+
+impl<B: Board> GameRecord<B> {
+    pub fn new() -> Self {
+        Self::from_position(GameRepr::new())
+    }
+
+    pub fn from_position(start: GameRepr<B>) -> Self {
+        GameRecord { headers: Vec::new(), start, moves: Vec::new() }
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /**
+     * Set a header, replacing any existing value.
+     */
+    pub fn set_header(&mut self, name: &str, value: impl Into<String>) {
+        let value = value.into();
+        match self.headers.iter_mut().find(|(key, _)| key == name) {
+            Some((_, old)) => *old = value,
+            None => self.headers.push((name.to_string(), value)),
+        }
+    }
+
+    /**
+     * Add a move to the end of the game.  The move is not checked.
+     */
+    pub fn push(&mut self, mov: impl Into<RecordedMove>) {
+        self.moves.push(mov.into());
+    }
+
+    /**
+     * The position after all the moves have been played.
+     */
+    pub fn position(&self) -> GameRepr<B> {
+        self.moves.iter().fold(self.start.clone(), |game, mov| game.apply(mov.mov))
+    }
+
+    /**
+     * Every move played, including passes.
+     */
+    pub fn all_moves(&self) -> Vec<Move> {
+        self.moves.iter().map(|mov| mov.mov).collect()
+    }
+
+    /**
+     * Set the result and score headers from the final position.  The result is "*" if the game
+     * isn't over.
+     */
+    pub fn set_result(&mut self) {
+        let position = self.position();
+        let (black, white) = position.scores();
+        let result = if !position.is_game_over() {
+            "*"
+        } else if black > white {
+            "1-0"
+        } else if black < white {
+            "0-1"
+        } else {
+            "1/2-1/2"
+        };
+        self.set_header(RESULT, result);
+        self.set_header(SCORE, format!("{black}-{white}"));
+    }
+}
+
+impl<B: Board> Default for GameRecord<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Board> Debug for GameRecord<B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GameRecord")
+            .field("headers", &self.headers)
+            .field("start", &self.start)
+            .field("moves", &self.moves)
+            .finish()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RecordErrorKind {
+    InvalidHeader(String),
+    InvalidPosition(ObfErrorKind),
+    UnterminatedComment,
+    InvalidAnnotation(String),
+    Move(TranscriptError),
+}
+
+/**
+ * An error in a record, with the line it was found on (starting from 1).
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordError {
+    pub line_number: usize,
+    pub kind: RecordErrorKind,
+}
+
+impl Display for RecordError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: ", self.line_number)?;
+        match &self.kind {
+            RecordErrorKind::InvalidHeader(line) => write!(f, "invalid header \"{line}\""),
+            RecordErrorKind::InvalidPosition(err) => write!(f, "invalid position: {err}"),
+            RecordErrorKind::UnterminatedComment => write!(f, "comment is not closed by \"}}\""),
+            RecordErrorKind::InvalidAnnotation(text) => write!(f, "invalid annotation \"{text}\""),
+            RecordErrorKind::Move(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for RecordError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            RecordErrorKind::Move(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * Put a backslash before each backslash and `special` character, for text inside quotes or braces.
+ */
+fn escape(value: &str, special: char) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if ch == '\\' || ch == special {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => unescaped.extend(chars.next()),
+            _ => unescaped.push(ch),
+        }
+    }
+    unescaped
+}
+
+fn parse_header(line: &str) -> Option<(String, String)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    let (name, value) = inner.split_once(char::is_whitespace)?;
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    Some((name.to_string(), unescape(value)))
+}
+
+/**
+ * The position of the "}" that closes a comment, skipping any escaped ones.
+ */
+fn comment_end(text: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, ch) in text.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '}' => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/**
+ * Pick the "[%eval ...]" and "[%time ...]" annotations out of a comment, leaving the rest of its
+ * text as the comment.
+ */
+fn parse_comment(text: &str, mov: &mut RecordedMove) -> Result<(), RecordErrorKind> {
+    let mut rest = text;
+    let mut comment = String::new();
+    while let Some(start) = rest.find("[%") {
+        comment.push_str(&rest[..start]);
+        let end = rest[start..].find(']')
+            .ok_or_else(|| RecordErrorKind::InvalidAnnotation(rest[start..].to_string()))?;
+        let annotation = &rest[start + 2..start + end];
+        let invalid = || RecordErrorKind::InvalidAnnotation(annotation.to_string());
+        let (name, value) = annotation.split_once(' ').ok_or_else(invalid)?;
+        let value: f64 = value.trim().parse().map_err(|_| invalid())?;
+        match name {
+            "eval" => mov.eval = Some(value),
+            "time" => mov.time = Some(value),
+            _ => return Err(invalid()),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    comment.push_str(rest);
+
+    let comment = unescape(&comment).split_whitespace().collect::<Vec<_>>().join(" ");
+    mov.comment = if comment.is_empty() { None } else { Some(comment) };
+    Ok(())
+}
+
+/**
+ * Reads records one at a time from a text that may hold many.
+ */
+struct RecordParser<'a> {
+    rest: &'a str,
+    line_number: usize,
+}
+
+impl<'a> RecordParser<'a> {
+    fn skip_whitespace(&mut self) {
+        let trimmed = self.rest.trim_start();
+        self.advance(self.rest.len() - trimmed.len());
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let (taken, rest) = self.rest.split_at(len);
+        self.line_number += taken.matches('\n').count();
+        self.rest = rest;
+        taken
+    }
+
+    fn error(&self, kind: RecordErrorKind) -> RecordError {
+        RecordError { line_number: self.line_number, kind }
+    }
+
+    fn parse<B: Board>(&mut self) -> Result<GameRecord<B>, RecordError> {
+        let mut headers = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if !self.rest.starts_with('[') { break }
+            let len = self.rest.find('\n').unwrap_or(self.rest.len());
+            let line = self.advance(len).trim();
+            let header = parse_header(line)
+                .ok_or_else(|| self.error(RecordErrorKind::InvalidHeader(line.to_string())))?;
+            headers.push(header);
+        }
+
+        let start = match headers.iter().find(|(name, _)| name == POSITION) {
+            Some((_, position)) => parse_position(position)
+                .map_err(|err| self.error(RecordErrorKind::InvalidPosition(err.kind)))?,
+            None => GameRepr::new(),
+        };
+        let mut record = GameRecord { headers, start, moves: Vec::new() };
+
+        let mut game = record.start.clone();
+        let mut played = Vec::new();
+        let mut move_number = 0;
+        loop {
+            self.skip_whitespace();
+            if self.rest.is_empty() { break }
+
+            if let Some(comment) = self.rest.strip_prefix('{') {
+                let end = comment_end(comment).ok_or_else(|| self.error(RecordErrorKind::UnterminatedComment))?;
+                let text = &comment[..end];
+                let mov = record.moves.last_mut()
+                    .ok_or_else(|| self.error(RecordErrorKind::InvalidAnnotation(text.to_string())))?;
+                parse_comment(text, mov).map_err(|kind| self.error(kind))?;
+                self.advance(end + 2);
+                continue;
+            }
+
+            let len = self.rest.find(|ch: char| ch.is_whitespace() || ch == '{').unwrap_or(self.rest.len());
+            let token = self.advance(len);
+            if RESULTS.contains(&token) { break }
+
+            /* Move numbers such as "12." or "12..." */
+            let token = token.trim_start_matches(|ch: char| ch.is_ascii_digit() || ch == '.');
+            if token.is_empty() { continue }
+
+            move_number += 1;
+            let move_error = |kind| self.error(RecordErrorKind::Move(TranscriptError {
+                move_number,
+                text: token.to_string(),
+                kind,
+            }));
+            if game.is_game_over() {
+                return Err(move_error(TranscriptErrorKind::GameOver));
+            }
+            let mov = Move::parse(token, game.next_turn)
+                .map_err(|err| move_error(TranscriptErrorKind::InvalidMove(err)))?;
+            let before = played.len();
+            play_recorded_move(&mut game, &mut played, mov, None).map_err(move_error)?;
+            record.moves.extend(played[before..].iter().map(|mov| RecordedMove::from(*mov)));
+        }
+
+        Ok(record)
+    }
+}
+
+/**
+ * Parse a single game record.
+ */
+pub fn parse_record<B: Board>(text: &str) -> Result<GameRecord<B>, RecordError> {
+    RecordParser { rest: text, line_number: 1 }.parse()
+}
+
+/**
+ * Parse every game record in a text, such as a file written by `write_records`.  Each record must
+ * end with its result.
+ */
+pub fn parse_records<B: Board>(text: &str) -> Result<Vec<GameRecord<B>>, RecordError> {
+    let mut parser = RecordParser { rest: text, line_number: 1 };
+    let mut records = Vec::new();
+    loop {
+        parser.skip_whitespace();
+        if parser.rest.is_empty() { break }
+        records.push(parser.parse()?);
+    }
+    Ok(records)
+}
+
+fn write_move(mov: &RecordedMove) -> String {
+    let mut text = if mov.mov.is_pass() {
+        mov.mov.to_string()
+    } else {
+        mov.mov.to_string().to_ascii_lowercase()
+    };
+
+    let mut annotations = Vec::new();
+    if let Some(eval) = mov.eval { annotations.push(format!("[%eval {eval:+}]")); }
+    if let Some(time) = mov.time { annotations.push(format!("[%time {time}]")); }
+    if let Some(comment) = &mov.comment { annotations.push(escape(comment, '}')); }
+    if !annotations.is_empty() {
+        write!(text, " {{{}}}", annotations.join(" ")).unwrap();
+    }
+    text
+}
+
+/**
+ * Write a game record.  The moves are wrapped to fit in 80 columns where possible.
+ */
+pub fn write_record<B: Board>(record: &GameRecord<B>) -> String {
+    let mut text = String::new();
+    for (name, value) in &record.headers {
+        if name != POSITION {
+            writeln!(text, "[{name} \"{}\"]", escape(value, '"')).unwrap();
+        }
+    }
+    let start = write_position(&record.start);
+    if start != write_position(&GameRepr::<B>::new()) {
+        writeln!(text, "[{POSITION} \"{start}\"]").unwrap();
+    }
+    text.push('\n');
+
+    /* Each move number is kept on the same line as its move */
+    let mut tokens = Vec::new();
+    let mut number = 1;
+    for (i, mov) in record.moves.iter().enumerate() {
+        match mov.mov.player {
+            Colour::Black => tokens.push(format!("{number}. {}", write_move(mov))),
+            Colour::White if i == 0 => tokens.push(format!("{number}... {}", write_move(mov))),
+            Colour::White => tokens.push(write_move(mov)),
+        }
+        if mov.mov.player == Colour::White {
+            number += 1;
+        }
+    }
+    tokens.push(record.header(RESULT).filter(|result| RESULTS.contains(result)).unwrap_or("*").to_string());
+
+    let mut line_length = 0;
+    for token in tokens {
+        if line_length > 0 && line_length + 1 + token.len() > LINE_LENGTH {
+            text.push('\n');
+            line_length = 0;
+        } else if line_length > 0 {
+            text.push(' ');
+            line_length += 1;
+        }
+        text.push_str(&token);
+        line_length += token.len();
+    }
+    text.push('\n');
+    text
+}
+
+/**
+ * Write many game records, separated by blank lines.
+ */
+pub fn write_records<B: Board>(records: &[GameRecord<B>]) -> String {
+    records.iter().map(write_record).collect::<Vec<_>>().join("\n")
+}
@@ -0,0 +1,83 @@
+/*!
+ * Serde support, enabled with the `serde` feature.
+ *
+ * Everything is written as short, readable strings:
+ *
+ * - A move is written with its player, as in `{"player": "black", "move": "d3"}`.  The move is
+ *   always written in lower case, and a pass is "pa".  Either case is read back, for the player
+ *   given.
+ * - A board is written as one character per square, as in the `obf` module.
+ * - A game is written as an `obf` position, the board followed by the player to move.
+ */
+
+use std::fmt::Display;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Board, Colour, GameRepr, Move};
+use crate::bitboardgame::BitBoardBoard;
+use crate::default::DefaultBoard;
+use crate::obf::{parse_board, parse_position, write_board, write_position};
+use crate::tableboard::TableBoard;
+
+fn deserialize_with<'de, D, T, E>(deserializer: D, parse: impl FnOnce(&str) -> Result<T, E>) -> Result<T, D::Error>
+where D: Deserializer<'de>, E: Display {
+    let text = String::deserialize(deserializer)?;
+    parse(&text).map_err(D::Error::custom)
+}
+
+/**
+ * How a move is written: its player, and the move itself in notation.
+ */
+#[derive(Serialize, Deserialize)]
+struct MoveRepr {
+    player: Colour,
+    #[serde(rename = "move")]
+    text: String,
+}
+
+impl Serialize for Move {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        MoveRepr { player: self.player, text: self.to_string().to_ascii_lowercase() }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Move {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = MoveRepr::deserialize(deserializer)?;
+        Move::parse(&repr.text, repr.player).map_err(D::Error::custom)
+    }
+}
+
+impl<B: Board> Serialize for GameRepr<B> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&write_position(self))
+    }
+}
+
+impl<'de, B: Board> Deserialize<'de> for GameRepr<B> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_with(deserializer, |text| parse_position(text).map_err(|err| err.kind))
+    }
+}
+
+macro_rules! impl_board_serde {
+    ($board:ident) => {
+        impl<const N: usize> Serialize for $board<N> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&write_board(self))
+            }
+        }
+
+        impl<'de, const N: usize> Deserialize<'de> for $board<N> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserialize_with(deserializer, parse_board)
+            }
+        }
+    };
+}
+
+impl_board_serde!(DefaultBoard);
+impl_board_serde!(BitBoardBoard);
+impl_board_serde!(TableBoard);
@@ -0,0 +1,242 @@
+/*!
+ * Short codes for sharing positions and games, safe to put in a URL or paste into a chat.
+ *
+ * Codes are written in the URL-safe base 64 alphabet, "A" to "Z", "a" to "z", "0" to "9", "-"
+ * and "_", with a letter in front saying what kind of code it is:
+ *
+ * - "p" and a position: the board size, the player to move (0 for Black, 1 for White), then two
+ *   bits per square row by row from A1 (0 for empty, 1 for Black, 2 for White), four squares to a
+ *   byte starting from the low bits.  This is about 24 characters for an 8x8 board.
+ * - "g" and a game: the moves, each written as one character holding `row * size + col`, or two
+ *   characters on boards larger than 8x8.  Passes are left out.  A game that doesn't start from
+ *   the usual position has its start position, in the same form as a "p" code, and a "." before
+ *   its moves.
+ */
+
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+use crate::{Board, Colour, GameRepr, Move, Pos};
+use crate::default::DefaultBoard;
+use crate::notation::{play_recorded_move, TranscriptError};
+use crate::obf::write_position;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+const POSITION_CODE: char = 'p';
+const GAME_CODE: char = 'g';
+const START_SEPARATOR: char = '.';
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ShareError {
+    Empty,
+    UnknownKind(char),
+    /**
+     * A character outside the alphabet, at the given index in the code (starting from 0).
+     */
+    InvalidCharacter { index: usize, ch: char },
+    WrongBoardSize { expected: Pos, found: u8 },
+    InvalidPosition,
+    Move(TranscriptError),
+}
+
+impl Display for ShareError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShareError::Empty => write!(f, "no code given"),
+            ShareError::UnknownKind(ch) => write!(f, "unknown kind of code '{ch}'"),
+            ShareError::InvalidCharacter { index, ch } => write!(f, "invalid character '{ch}' at {index}"),
+            ShareError::WrongBoardSize { expected, found } =>
+                write!(f, "expected a {expected}x{expected} board, found {found}x{found}"),
+            ShareError::InvalidPosition => write!(f, "invalid position"),
+            ShareError::Move(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for ShareError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ShareError::Move(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * A shared game: a start position and the moves played from it, including passes.  A shared
+ * position has no moves.
+ */
+#[derive(Clone)]
+pub struct SharedGame<B: Board = DefaultBoard> {
+    pub start: GameRepr<B>,
+    pub moves: Vec<Move>,
+}
+
+impl<B: Board> SharedGame<B> {
+    /**
+     * The position after all the moves have been played.
+     */
+    pub fn position(&self) -> GameRepr<B> {
+        self.moves.iter().fold(self.start.clone(), |game, mov| game.apply(*mov))
+    }
+}
+
+impl<B: Board> Debug for SharedGame<B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedGame")
+            .field("start", &self.start)
+            .field("moves", &self.moves)
+            .finish()
+    }
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, &byte)| bits | (byte as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            text.push(ALPHABET[(bits >> (18 - 6 * i)) as usize & 63] as char);
+        }
+    }
+    text
+}
+
+fn decode_char(ch: char, index: usize) -> Result<u32, ShareError> {
+    ALPHABET.iter()
+        .position(|&letter| letter as char == ch)
+        .map(|value| value as u32)
+        .ok_or(ShareError::InvalidCharacter { index, ch })
+}
+
+/**
+ * Decode base 64 text which starts at `offset` in the code.
+ */
+fn decode_base64(text: &str, offset: usize) -> Result<Vec<u8>, ShareError> {
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let (mut bits, mut num_bits) = (0u32, 0);
+    for (i, ch) in text.chars().enumerate() {
+        bits = bits << 6 | decode_char(ch, offset + i)?;
+        num_bits += 6;
+        if num_bits >= 8 {
+            num_bits -= 8;
+            bytes.push((bits >> num_bits) as u8);
+            bits &= (1 << num_bits) - 1;
+        }
+    }
+    Ok(bytes)
+}
+
+fn position_bytes<B: Board>(game: &GameRepr<B>) -> Vec<u8> {
+    let size = B::SIZE as usize;
+    let mut bytes = vec![0; 2 + (size * size).div_ceil(4)];
+    bytes[0] = B::SIZE as u8;
+    bytes[1] = match game.next_turn {
+        Colour::Black => 0,
+        Colour::White => 1,
+    };
+    for i in 0..size * size {
+        let value = match game.board.get((i / size) as Pos, (i % size) as Pos) {
+            None => 0,
+            Some(Colour::Black) => 1,
+            Some(Colour::White) => 2,
+        };
+        bytes[2 + i / 4] |= value << (2 * (i % 4));
+    }
+    bytes
+}
+
+fn decode_position<B: Board>(text: &str, offset: usize) -> Result<GameRepr<B>, ShareError> {
+    let bytes = decode_base64(text, offset)?;
+    let size = B::SIZE as usize;
+    match bytes.first() {
+        Some(&found) if found as Pos != B::SIZE => return Err(ShareError::WrongBoardSize { expected: B::SIZE, found }),
+        _ if bytes.len() != 2 + (size * size).div_ceil(4) => return Err(ShareError::InvalidPosition),
+        _ => (),
+    }
+
+    let mut game: GameRepr<B> = GameRepr::empty();
+    game.next_turn = match bytes[1] {
+        0 => Colour::Black,
+        1 => Colour::White,
+        _ => return Err(ShareError::InvalidPosition),
+    };
+    for i in 0..size * size {
+        let piece = match (bytes[2 + i / 4] >> (2 * (i % 4))) & 3 {
+            0 => None,
+            1 => Some(Colour::Black),
+            2 => Some(Colour::White),
+            _ => return Err(ShareError::InvalidPosition),
+        };
+        game.board.set((i / size) as Pos, (i % size) as Pos, piece);
+    }
+    Ok(game)
+}
+
+/**
+ * The number of characters used for each move.
+ */
+fn move_len<B: Board>() -> usize {
+    if B::SIZE <= 8 { 1 } else { 2 }
+}
+
+/**
+ * The code for a position.
+ */
+pub fn encode_position<B: Board>(game: &GameRepr<B>) -> String {
+    format!("{POSITION_CODE}{}", encode_base64(&position_bytes(game)))
+}
+
+/**
+ * The code for a game played from `start`.  The moves aren't checked.
+ */
+pub fn encode_game<B: Board>(start: &GameRepr<B>, moves: &[Move]) -> String {
+    let mut code = GAME_CODE.to_string();
+    if write_position(start) != write_position(&GameRepr::<B>::new()) {
+        code.push_str(&encode_base64(&position_bytes(start)));
+        code.push(START_SEPARATOR);
+    }
+    for mov in moves.iter().filter(|mov| !mov.is_pass()) {
+        let square = (mov.row as usize) * (B::SIZE as usize) + mov.col as usize;
+        if move_len::<B>() == 2 {
+            code.push(ALPHABET[square / 64] as char);
+        }
+        code.push(ALPHABET[square % 64] as char);
+    }
+    code
+}
+
+/**
+ * Read a position or game code.  The moves of a game are checked, and its passes put back.
+ */
+pub fn decode_share_code<B: Board>(code: &str) -> Result<SharedGame<B>, ShareError> {
+    let code = code.trim();
+    let mut chars = code.chars();
+    let kind = chars.next().ok_or(ShareError::Empty)?;
+    let body = chars.as_str();
+    let offset = kind.len_utf8();
+
+    match kind {
+        POSITION_CODE => Ok(SharedGame { start: decode_position(body, offset)?, moves: Vec::new() }),
+        GAME_CODE => {
+            let (start, moves, moves_offset) = match body.split_once(START_SEPARATOR) {
+                Some((start, moves)) => (decode_position(start, offset)?, moves, offset + start.len() + 1),
+                None => (GameRepr::new(), body, offset),
+            };
+
+            let mut game = start.clone();
+            let mut played = Vec::new();
+            let chars: Vec<char> = moves.chars().collect();
+            for (i, chunk) in chars.chunks(move_len::<B>()).enumerate() {
+                let index = moves_offset + i * move_len::<B>();
+                let square = chunk.iter().enumerate()
+                    .try_fold(0, |square, (j, &ch)| decode_char(ch, index + j).map(|value| square * 64 + value as usize))?;
+                let mov = Move { player: game.next_turn, row: (square / B::SIZE as usize) as Pos, col: (square % B::SIZE as usize) as Pos };
+                play_recorded_move(&mut game, &mut played, mov, None)
+                    .map_err(|kind| ShareError::Move(TranscriptError { move_number: i + 1, text: chunk.iter().collect(), kind }))?;
+            }
+            Ok(SharedGame { start, moves: played })
+        }
+        _ => Err(ShareError::UnknownKind(kind)),
+    }
+}
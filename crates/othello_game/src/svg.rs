@@ -0,0 +1,207 @@
+/*!
+ * SVG diagrams of positions, enabled with the `svg` feature.
+ *
+ * A diagram shows the board with its discs, and optionally the coordinates, markers on the legal
+ * moves, a highlight on the last move, numbers on the discs of a sequence of moves, and arrows and
+ * labels for annotation.  The output is plain text, so it needs no window and can be compared in
+ * tests.
+ */
+
+use std::fmt::Write;
+
+use crate::{Board, Colour, Game, GameRepr, Move, Pos};
+
+const BOARD_COLOUR: &str = "#2e7d32";
+const LINE_COLOUR: &str = "#000000";
+const HIGHLIGHT_COLOUR: &str = "#fdd835";
+const MARKER_COLOUR: &str = "#000000";
+
+/**
+ * An arrow or label drawn over the board, in any CSS colour.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Annotation {
+    Arrow { from: (Pos, Pos), to: (Pos, Pos), colour: String },
+    Label { square: (Pos, Pos), text: String, colour: String },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgOptions {
+    /**
+     * The width of a square, in pixels.
+     */
+    pub square_size: u32,
+    /**
+     * Draw the column letters and row numbers around the board.
+     */
+    pub coordinates: bool,
+    /**
+     * Mark the squares the player to move can play on.
+     */
+    pub legal_moves: bool,
+    pub last_move: Option<Move>,
+    /**
+     * Moves to number on their discs, counting from `first_move_number`.  Passes aren't numbered.
+     */
+    pub numbered_moves: Vec<Move>,
+    pub first_move_number: u32,
+    pub annotations: Vec<Annotation>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            square_size: 40,
+            coordinates: true,
+            legal_moves: false,
+            last_move: None,
+            numbered_moves: Vec::new(),
+            first_move_number: 1,
+            annotations: Vec::new(),
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/**
+ * Positions on the drawing, in pixels.
+ */
+struct Layout {
+    square_size: u32,
+    margin: u32,
+}
+
+impl Layout {
+    fn left(&self, col: Pos) -> u32 {
+        self.margin + col as u32 * self.square_size
+    }
+
+    fn top(&self, row: Pos) -> u32 {
+        self.margin + row as u32 * self.square_size
+    }
+
+    fn centre(&self, (row, col): (Pos, Pos)) -> (u32, u32) {
+        (self.left(col) + self.square_size / 2, self.top(row) + self.square_size / 2)
+    }
+
+    fn font_size(&self) -> u32 {
+        self.square_size * 2 / 5
+    }
+
+    fn text(&self, svg: &mut String, (x, y): (u32, u32), text: &str, colour: &str) {
+        writeln!(svg, r#"<text x="{x}" y="{y}" font-family="sans-serif" font-size="{}" text-anchor="middle" dominant-baseline="central" fill="{colour}">{}</text>"#,
+                 self.font_size(), escape(text)).unwrap();
+    }
+}
+
+/**
+ * Draw a position.
+ */
+pub fn render_svg<B: Board>(game: &GameRepr<B>, options: &SvgOptions) -> String {
+    let size = B::SIZE;
+    let s = options.square_size;
+    let layout = Layout { square_size: s, margin: if options.coordinates { s / 2 } else { 0 } };
+    let board_width = size as u32 * s;
+    let width = board_width + 2 * layout.margin;
+
+    let mut svg = String::new();
+    writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{width}" viewBox="0 0 {width} {width}">"#).unwrap();
+
+    let has_arrows = options.annotations.iter().any(|annotation| matches!(annotation, Annotation::Arrow { .. }));
+    if has_arrows {
+        writeln!(svg, r#"<defs><marker id="arrowhead" markerWidth="4" markerHeight="4" refX="2" refY="2" orient="auto"><path d="M0,0 L4,2 L0,4 z" fill="context-stroke"/></marker></defs>"#).unwrap();
+    }
+
+    /* The board and its grid */
+    writeln!(svg, r#"<rect x="{0}" y="{0}" width="{1}" height="{1}" fill="{BOARD_COLOUR}"/>"#, layout.margin, board_width).unwrap();
+    if let Some(mov) = options.last_move.filter(|mov| !mov.is_pass()) {
+        writeln!(svg, r#"<rect x="{}" y="{}" width="{s}" height="{s}" fill="{HIGHLIGHT_COLOUR}" fill-opacity="0.6"/>"#,
+                 layout.left(mov.col), layout.top(mov.row)).unwrap();
+    }
+    for i in 0..=size {
+        let (start, end, at) = (layout.margin, layout.margin + board_width, layout.top(i));
+        writeln!(svg, r#"<line x1="{start}" y1="{at}" x2="{end}" y2="{at}" stroke="{LINE_COLOUR}"/>"#).unwrap();
+        writeln!(svg, r#"<line x1="{at}" y1="{start}" x2="{at}" y2="{end}" stroke="{LINE_COLOUR}"/>"#).unwrap();
+    }
+
+    /* The dots marking the corners of the centre 4x4 */
+    if size == 8 {
+        for (row, col) in [(2, 2), (2, 6), (6, 2), (6, 6)] {
+            writeln!(svg, r#"<circle cx="{}" cy="{}" r="{}" fill="{LINE_COLOUR}"/>"#, layout.left(col), layout.top(row), s / 16).unwrap();
+        }
+    }
+
+    if options.coordinates {
+        for i in 0..size {
+            let (x, y) = layout.centre((i, i));
+            let column = char::from(b'a' + i as u8).to_string();
+            layout.text(&mut svg, (x, layout.margin / 2), &column, LINE_COLOUR);
+            layout.text(&mut svg, (layout.margin / 2, y), &(i + 1).to_string(), LINE_COLOUR);
+        }
+    }
+
+    for row in 0..size {
+        for col in 0..size {
+            if let Some(colour) = game.board.get(row, col) {
+                let (fill, stroke) = match colour {
+                    Colour::Black => ("#000000", "#000000"),
+                    Colour::White => ("#ffffff", "#000000"),
+                };
+                let (x, y) = layout.centre((row, col));
+                writeln!(svg, r#"<circle cx="{x}" cy="{y}" r="{}" fill="{fill}" stroke="{stroke}"/>"#, s * 21 / 50).unwrap();
+            }
+        }
+    }
+
+    if options.legal_moves {
+        for mov in game.valid_moves(game.next_turn) {
+            let (x, y) = layout.centre((mov.row, mov.col));
+            writeln!(svg, r#"<circle cx="{x}" cy="{y}" r="{}" fill="{MARKER_COLOUR}" fill-opacity="0.4"/>"#, s / 8).unwrap();
+        }
+    }
+
+    let numbered = options.numbered_moves.iter().filter(|mov| !mov.is_pass());
+    for (number, mov) in (options.first_move_number..).zip(numbered) {
+        let colour = match game.board.get(mov.row, mov.col) {
+            Some(Colour::Black) => "#ffffff",
+            _ => "#000000",
+        };
+        layout.text(&mut svg, layout.centre((mov.row, mov.col)), &number.to_string(), colour);
+    }
+
+    for annotation in &options.annotations {
+        match annotation {
+            Annotation::Arrow { from, to, colour } => {
+                let ((x1, y1), (x2, y2)) = (layout.centre(*from), layout.centre(*to));
+                writeln!(svg, r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="{}" stroke-width="{}" stroke-opacity="0.8" marker-end="url(#arrowhead)"/>"#,
+                         escape(colour), s / 10).unwrap();
+            }
+            Annotation::Label { square, text, colour } => {
+                layout.text(&mut svg, layout.centre(*square), text, &escape(colour));
+            }
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/**
+ * Draw the position after playing `moves` from `start`, with the moves numbered and the last one
+ * highlighted.  Any numbered moves or last move in `options` are replaced.
+ */
+pub fn render_game_svg<B: Board>(start: &GameRepr<B>, moves: &[Move], options: &SvgOptions) -> String {
+    let game = moves.iter().fold(start.clone(), |game, mov| game.apply(*mov));
+    let options = SvgOptions {
+        last_move: moves.iter().rev().find(|mov| !mov.is_pass()).copied(),
+        numbered_moves: moves.to_vec(),
+        ..options.clone()
+    };
+    render_svg(&game, &options)
+}
@@ -0,0 +1,133 @@
+use crate::{Board, Colour, Move, Pos};
+
+/**
+ * One of the eight symmetries of the board: the rotations and reflections that map the board onto
+ * itself.  Rotations are clockwise.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Transform {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipVertical,
+    FlipHorizontal,
+    FlipDiagonal,
+    FlipAntiDiagonal,
+}
+
+impl Transform {
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity, Transform::Rotate90, Transform::Rotate180, Transform::Rotate270,
+        Transform::FlipVertical, Transform::FlipHorizontal, Transform::FlipDiagonal, Transform::FlipAntiDiagonal,
+    ];
+
+    /**
+     * The transform that undoes this one.
+     */
+    pub fn inverse(self) -> Transform {
+        match self {
+            Transform::Rotate90 => Transform::Rotate270,
+            Transform::Rotate270 => Transform::Rotate90,
+            _ => self
+        }
+    }
+
+    /**
+     * Where the transform moves the given square on a board of the given size.
+     */
+    pub fn apply(self, row: Pos, col: Pos, size: Pos) -> (Pos, Pos) {
+        let last = size - 1;
+        match self {
+            Transform::Identity => (row, col),
+            Transform::Rotate90 => (col, last - row),
+            Transform::Rotate180 => (last - row, last - col),
+            Transform::Rotate270 => (last - col, row),
+            Transform::FlipVertical => (last - row, col),
+            Transform::FlipHorizontal => (row, last - col),
+            Transform::FlipDiagonal => (col, row),
+            Transform::FlipAntiDiagonal => (last - col, last - row),
+        }
+    }
+
+    pub fn apply_move(self, mov: Move, size: Pos) -> Move {
+        if mov.is_pass() { return mov }
+        let (row, col) = self.apply(mov.row, mov.col, size);
+        Move { row, col, ..mov }
+    }
+}
+
+/**
+ * Apply a transform to any board, one square at a time.
+ */
+pub(crate) fn transform_board<B: Board>(board: &B, transform: Transform) -> B {
+    let mut new_board = B::default();
+    for i in 0..B::SIZE {
+        for j in 0..B::SIZE {
+            let (row, col) = transform.apply(i, j, B::SIZE);
+            new_board.set(row, col, board.get(i, j));
+        }
+    }
+    new_board
+}
+
+/**
+ * Pick the canonical form of a board: the transformed board with the lowest ordering key, with
+ * ties going to the first transform in `Transform::ALL`.
+ */
+pub(crate) fn canonical_by_key<B: Board, K: Ord>(board: &B, key: impl Fn(&B) -> K) -> (B, Transform) {
+    Transform::ALL.iter()
+        .map(|t| (board.transform(*t), *t))
+        .min_by_key(|(b, _)| key(b))
+        .expect("at least one transform")
+}
+
+/**
+ * An ordering key for boards that is the same for any two boards with the same pieces.  Boards
+ * up to 8x8 are ordered the same way as their bitboards.
+ */
+pub(crate) fn board_order_key<B: Board>(board: &B) -> (u128, u128) {
+    let mut blacks = 0;
+    let mut whites = 0;
+    let stride = B::SIZE.max(8) as u32;
+    for i in 0..B::SIZE {
+        for j in 0..B::SIZE {
+            let bit = 1u128 << (i as u32 * stride + j as u32);
+            match board.get(i, j) {
+                Some(Colour::Black) => blacks |= bit,
+                Some(Colour::White) => whites |= bit,
+                None => ()
+            }
+        }
+    }
+    (blacks, whites)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_inverse() {
+        for t in Transform::ALL {
+            for (row, col) in [(0, 0), (0, 7), (2, 5), (6, 1)] {
+                let (r2, c2) = t.apply(row, col, 8);
+                assert_eq!((row, col), t.inverse().apply(r2, c2, 8), "{t:?}");
+                let (r2, c2) = t.apply(row, col, 10);
+                assert_eq!((row, col), t.inverse().apply(r2, c2, 10), "{t:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        assert_eq!((1, 7), Transform::Rotate90.apply(0, 1, 8));
+        assert_eq!((7, 6), Transform::Rotate180.apply(0, 1, 8));
+        assert_eq!((6, 0), Transform::Rotate270.apply(0, 1, 8));
+        assert_eq!((7, 1), Transform::FlipVertical.apply(0, 1, 8));
+        assert_eq!((0, 6), Transform::FlipHorizontal.apply(0, 1, 8));
+        assert_eq!((1, 0), Transform::FlipDiagonal.apply(0, 1, 8));
+        assert_eq!((6, 7), Transform::FlipAntiDiagonal.apply(0, 1, 8));
+        assert_eq!((1, 5), Transform::Rotate90.apply(0, 1, 6));
+    }
+}
@@ -0,0 +1,308 @@
+/*!
+ * A bitboard that works out flips with lookup tables, in the style of Edax
+ * (https://github.com/abulmo/edax-reversi).
+ *
+ * Each of the four lines through the move square (its row, column, diagonal and anti-diagonal) is
+ * gathered into an 8-bit pattern, indexed by column (or by row, for the column line).  One table
+ * gives the squares at the far end of each run of the opponent's pieces next to the move; any of
+ * those holding the player's own piece outflanks the run.  A second table gives the pieces flipped
+ * by those outflanking squares, which are then scattered back onto the board.
+ */
+
+use std::hash::{Hash, Hasher};
+
+use crate::{Board, Colour, Move, Pos, Score};
+use crate::bitboard::BitBoard;
+use crate::bitboardgame::{BitBoardBoard, Moves};
+use crate::symmetry::Transform;
+
+/**
+ * For a move at index `x` in a line, and the opponent's pieces on the six inner squares of the
+ * line, the squares that would outflank the opponent's pieces next to the move.
+ */
+const OUTFLANK: [[u8; 64]; 8] = outflank_table();
+
+/**
+ * For a move at index `x` in a line, and a set of outflanking squares, the squares flipped.
+ */
+const FLIPPED: [[u8; 256]; 8] = flipped_table();
+
+/**
+ * The column bitboard with row `r` set for each bit `r` in the index.
+ */
+const COLUMN_SCATTER: [u64; 256] = column_scatter_table();
+
+const DIAGONAL_MASKS: [u64; 64] = line_masks(1);
+const ANTI_DIAGONAL_MASKS: [u64; 64] = line_masks(-1);
+
+const COLUMN_0: u64 = 0x0101010101010101;
+
+const fn outflank_table() -> [[u8; 64]; 8] {
+    let mut table = [[0; 64]; 8];
+    let mut x = 0;
+    while x < 8 {
+        let mut inner = 0;
+        while inner < 64 {
+            let theirs = (inner << 1) as u8;
+            let mut outflank = 0u8;
+
+            let mut j = x + 1;
+            while j < 7 && theirs & (1 << j) != 0 { j += 1; }
+            if j > x + 1 && j <= 7 { outflank |= 1 << j; }
+
+            let mut j = x as i32 - 1;
+            while j > 0 && theirs & (1 << j) != 0 { j -= 1; }
+            if j < x as i32 - 1 && j >= 0 { outflank |= 1 << j; }
+
+            table[x][inner] = outflank;
+            inner += 1;
+        }
+        x += 1;
+    }
+    table
+}
+
+const fn flipped_table() -> [[u8; 256]; 8] {
+    let mut table = [[0; 256]; 8];
+    let mut x = 0;
+    while x < 8 {
+        let mut outflank = 0;
+        while outflank < 256 {
+            let mut flipped = 0u8;
+            let mut j = 0;
+            while j < 8 {
+                if outflank & (1 << j) != 0 {
+                    let (low, high) = if j < x { (j, x) } else { (x, j) };
+                    let mut k = low + 1;
+                    while k < high {
+                        flipped |= 1 << k;
+                        k += 1;
+                    }
+                }
+                j += 1;
+            }
+            table[x][outflank] = flipped;
+            outflank += 1;
+        }
+        x += 1;
+    }
+    table
+}
+
+const fn column_scatter_table() -> [u64; 256] {
+    let mut table = [0; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut row = 0;
+        while row < 8 {
+            if index & (1 << row) != 0 {
+                table[index] |= 1 << (row * 8);
+            }
+            row += 1;
+        }
+        index += 1;
+    }
+    table
+}
+
+/**
+ * For each square, the mask of the diagonal through it: going down and right when `col_step` is
+ * 1, or down and left when it is -1.
+ */
+const fn line_masks(col_step: i32) -> [u64; 64] {
+    let mut table = [0; 64];
+    let mut square = 0;
+    while square < 64 {
+        let (row, col) = ((square / 8) as i32, (square % 8) as i32);
+        let mut r = 0;
+        while r < 8 {
+            let c = col + (r - row) * col_step;
+            if c >= 0 && c < 8 {
+                table[square] |= 1 << (r * 8 + c);
+            }
+            r += 1;
+        }
+        square += 1;
+    }
+    table
+}
+
+#[inline(always)]
+fn line_flips(mine: u8, theirs: u8, x: usize) -> u8 {
+    let outflank = OUTFLANK[x][((theirs >> 1) & 0x3F) as usize] & mine;
+    FLIPPED[x][outflank as usize]
+}
+
+#[inline(always)]
+fn gather_diagonal(bits: u64, mask: u64) -> u8 {
+    ((bits & mask).wrapping_mul(COLUMN_0) >> 56) as u8
+}
+
+#[inline(always)]
+fn scatter_diagonal(line: u8, mask: u64) -> u64 {
+    (line as u64).wrapping_mul(COLUMN_0) & mask
+}
+
+#[inline(always)]
+fn gather_column(bits: u64, col: usize) -> u8 {
+    (((bits >> col) & COLUMN_0).wrapping_mul(0x0102040810204080) >> 56) as u8
+}
+
+/**
+ * The opponent's pieces flipped when a player with pieces `mine` moves at (row, col).
+ */
+pub fn flips(mine: BitBoard, theirs: BitBoard, row: Pos, col: Pos) -> BitBoard {
+    let (mine, theirs) = (mine.bits(), theirs.bits());
+    let (row, col) = (row as usize, col as usize);
+    let square = row * 8 + col;
+
+    let row_flips = line_flips((mine >> (row * 8)) as u8, (theirs >> (row * 8)) as u8, col);
+    let mut flips = (row_flips as u64) << (row * 8);
+
+    let col_flips = line_flips(gather_column(mine, col), gather_column(theirs, col), row);
+    flips |= COLUMN_SCATTER[col_flips as usize] << col;
+
+    let mask = DIAGONAL_MASKS[square];
+    let diag_flips = line_flips(gather_diagonal(mine, mask), gather_diagonal(theirs, mask), col);
+    flips |= scatter_diagonal(diag_flips, mask);
+
+    let mask = ANTI_DIAGONAL_MASKS[square];
+    let anti_flips = line_flips(gather_diagonal(mine, mask), gather_diagonal(theirs, mask), col);
+    flips |= scatter_diagonal(anti_flips, mask);
+
+    BitBoard::from_bits(flips)
+}
+
+/**
+ * A board that finds moves like `BitBoardBoard`, but applies them using lookup tables.  Like
+ * `BitBoardBoard`, it can be any size up to 8x8.
+ */
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TableBoard<const N: usize = 8>(BitBoardBoard<N>);
+
+impl<const N: usize> TableBoard<N> {
+    pub fn blacks(&self) -> BitBoard {
+        self.0.blacks()
+    }
+
+    pub fn whites(&self) -> BitBoard {
+        self.0.whites()
+    }
+}
+
+impl<const N: usize> Board for TableBoard<N> {
+    type MoveSet = Moves;
+
+    const SIZE: Pos = BitBoardBoard::<N>::SIZE;
+
+    fn is_valid_move(&self, mov: Move) -> bool {
+        let mov_bb = BitBoard::from((mov.row, mov.col));
+        if (mov_bb & BitBoardBoard::<N>::SQUARES).is_empty() || self.get(mov.row, mov.col).is_some() {
+            return false;
+        }
+        let (mine, theirs) = self.0.mine_and_theirs(mov.player);
+        !flips(mine, theirs, mov.row, mov.col).is_empty()
+    }
+
+    fn moves(&self, for_player: Colour) -> Self::MoveSet {
+        self.0.moves(for_player)
+    }
+
+    fn apply(&self, mov: Move) -> Self {
+        let (mine, theirs) = self.0.mine_and_theirs(mov.player);
+        TableBoard(self.0.with_flips(mov, flips(mine, theirs, mov.row, mov.col)))
+    }
+
+    fn get(&self, row: Pos, col: Pos) -> Option<Colour> {
+        self.0.get(row, col)
+    }
+
+    fn set(&mut self, row: Pos, col: Pos, value: Option<Colour>) {
+        self.0.set(row, col, value)
+    }
+
+    fn scores(&self) -> (Score, Score) {
+        self.0.scores()
+    }
+
+    fn zobrist_key(&self) -> u64 {
+        self.0.zobrist_key()
+    }
+
+    fn transform(&self, transform: Transform) -> Self {
+        TableBoard(self.0.transform(transform))
+    }
+
+    fn canonical(&self) -> (Self, Transform) {
+        let (board, transform) = self.0.canonical();
+        (TableBoard(board), transform)
+    }
+}
+
+impl<const N: usize> Hash for TableBoard<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{convert_board, random_board};
+    use crate::default::DefaultBoard;
+    use crate::movegen::FillBackend;
+    use super::*;
+
+    #[test]
+    fn test_gather_scatter() {
+        let bb = BitBoard::from(&[(0, 2), (3, 2), (7, 2), (3, 5)]);
+        assert_eq!(0b1000_1001, gather_column(bb.bits(), 2));
+        assert_eq!(BitBoard::from(&[(0, 2), (3, 2), (7, 2)]).bits(), COLUMN_SCATTER[0b1000_1001] << 2);
+
+        let mask = DIAGONAL_MASKS[3 * 8 + 2];
+        assert_eq!(BitBoard::from(&[(1, 0), (2, 1), (3, 2), (4, 3), (5, 4), (6, 5), (7, 6)]).bits(), mask);
+        let diagonal = BitBoard::from(&[(2, 1), (5, 4)]).bits();
+        assert_eq!(0b0001_0010, gather_diagonal(diagonal | bb.bits(), mask) & !0b100);
+        assert_eq!(diagonal, scatter_diagonal(0b0001_0010, mask));
+
+        let mask = ANTI_DIAGONAL_MASKS[3 * 8 + 2];
+        assert_eq!(BitBoard::from(&[(0, 5), (1, 4), (2, 3), (3, 2), (4, 1), (5, 0)]).bits(), mask);
+    }
+
+    #[test]
+    fn test_line_flips() {
+        /* Move at 0, opponent at 1 and 2, own piece at 3 */
+        assert_eq!(0b0000_0110, line_flips(0b0000_1000, 0b0000_0110, 0));
+        /* No own piece at the end */
+        assert_eq!(0, line_flips(0b0000_0000, 0b0000_0110, 0));
+        /* Both sides */
+        assert_eq!(0b0110_1100, line_flips(0b1000_0010, 0b0110_1100, 4));
+        /* Opponent runs off the end of the line */
+        assert_eq!(0, line_flips(0b0000_0001, 0b1110_0000, 4));
+    }
+
+    #[test]
+    fn test_random_boards() {
+        for _ in 0..1000 {
+            let board: TableBoard = random_board();
+            let default_board: DefaultBoard = convert_board(&board);
+
+            for player in [Colour::Black, Colour::White] {
+                let (mine, theirs) = board.0.mine_and_theirs(player);
+                for mov in board.moves(player) {
+                    let expected = FillBackend::Dumb7Fill.flips(mine, theirs, BitBoard::from((mov.row, mov.col)));
+                    assert_eq!(expected, flips(mine, theirs, mov.row, mov.col), "{board:?} {mov:?}");
+
+                    let applied: DefaultBoard = convert_board(&board.apply(mov));
+                    assert!(applied == default_board.apply(mov));
+                }
+
+                for row in 0..8 {
+                    for col in 0..8 {
+                        let mov = Move { player, row, col };
+                        assert_eq!(default_board.is_valid_move(mov), board.is_valid_move(mov));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,368 @@
+/*!
+ * Reading and writing the WTHOR database format of the Fédération Française d'Othello, used for
+ * its archive of tournament games.
+ *
+ * A database is three kinds of file, each starting with the same 16 byte header:
+ *
+ * - A ".wtb" file of games, each a 68 byte record: the tournament number, the black and white
+ *   player numbers (all little-endian u16), Black's actual and theoretical final disc counts, then
+ *   up to 60 moves written as `10 * row + col` counting from 1, so that A1 is 11 and H8 is 88.
+ *   Passes aren't recorded, and unused moves are 0.
+ * - A ".jou" file of player names, each 20 bytes.
+ * - A ".trn" file of tournament names, each 26 bytes.
+ *
+ * Names are Latin-1, padded with zero bytes.  Only games on 8x8 boards are supported.
+ */
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Read, Write};
+
+use crate::{Board, Game, GameRepr, Move, Pos, Score};
+use crate::bitboardgame::BitBoardBoard;
+use crate::notation::play_recorded_move;
+
+pub const HEADER_LEN: usize = 16;
+pub const GAME_RECORD_LEN: usize = 68;
+pub const PLAYER_RECORD_LEN: usize = 20;
+pub const TOURNAMENT_RECORD_LEN: usize = 26;
+
+const MAX_MOVES: usize = 60;
+
+#[derive(Debug)]
+pub enum WthorError {
+    Io(io::Error),
+    UnsupportedBoardSize(u8),
+    InvalidMove { game_number: usize, move_number: usize, value: u8 },
+    IllegalMove { game_number: usize, move_number: usize, mov: Move },
+}
+
+impl Display for WthorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WthorError::Io(err) => write!(f, "{err}"),
+            WthorError::UnsupportedBoardSize(size) => write!(f, "unsupported board size {size}"),
+            WthorError::InvalidMove { game_number, move_number, value } =>
+                write!(f, "game {game_number}: move {move_number} has invalid square {value}"),
+            WthorError::IllegalMove { game_number, move_number, mov } =>
+                write!(f, "game {game_number}: move {move_number} \"{mov}\" is illegal"),
+        }
+    }
+}
+
+impl Error for WthorError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WthorError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for WthorError {
+    fn from(err: io::Error) -> Self {
+        WthorError::Io(err)
+    }
+}
+
+/**
+ * The header shared by all WTHOR files.  `num_games` is used by game files, and `num_names` by
+ * player and tournament files.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WthorHeader {
+    pub created_year: u16,
+    pub created_month: u8,
+    pub created_day: u8,
+    pub num_games: u32,
+    pub num_names: u16,
+    pub game_year: u16,
+    pub board_size: u8,
+    pub solitaire: bool,
+    /**
+     * The number of empty squares at which the theoretical scores were worked out.
+     */
+    pub depth: u8,
+}
+
+impl Default for WthorHeader {
+    fn default() -> Self {
+        WthorHeader {
+            created_year: 0,
+            created_month: 0,
+            created_day: 0,
+            num_games: 0,
+            num_names: 0,
+            game_year: 0,
+            board_size: 8,
+            solitaire: false,
+            depth: 0,
+        }
+    }
+}
+
+impl WthorHeader {
+    pub fn read(reader: &mut impl Read) -> Result<WthorHeader, WthorError> {
+        let mut bytes = [0; HEADER_LEN];
+        reader.read_exact(&mut bytes)?;
+
+        /* Older files write 0 for the usual 8x8 board */
+        let board_size = if bytes[12] == 0 { 8 } else { bytes[12] };
+        Ok(WthorHeader {
+            created_year: bytes[0] as u16 * 100 + bytes[1] as u16,
+            created_month: bytes[2],
+            created_day: bytes[3],
+            num_games: u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+            num_names: u16::from_le_bytes([bytes[8], bytes[9]]),
+            game_year: u16::from_le_bytes([bytes[10], bytes[11]]),
+            board_size,
+            solitaire: bytes[13] != 0,
+            depth: bytes[14],
+        })
+    }
+
+    pub fn write(&self, writer: &mut impl Write) -> io::Result<()> {
+        let mut bytes = [0; HEADER_LEN];
+        bytes[0] = (self.created_year / 100) as u8;
+        bytes[1] = (self.created_year % 100) as u8;
+        bytes[2] = self.created_month;
+        bytes[3] = self.created_day;
+        bytes[4..8].copy_from_slice(&self.num_games.to_le_bytes());
+        bytes[8..10].copy_from_slice(&self.num_names.to_le_bytes());
+        bytes[10..12].copy_from_slice(&self.game_year.to_le_bytes());
+        bytes[12] = self.board_size;
+        bytes[13] = self.solitaire as u8;
+        bytes[14] = self.depth;
+        writer.write_all(&bytes)
+    }
+}
+
+/**
+ * A game from a WTHOR file.  Scores are Black's final disc counts: the actual score, and the
+ * theoretical score with perfect play from `depth` empty squares.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WthorGame {
+    pub tournament: u16,
+    pub black: u16,
+    pub white: u16,
+    pub score: u8,
+    pub theoretical_score: u8,
+    /**
+     * Every move played, including the passes, which aren't in the file.
+     */
+    pub moves: Vec<Move>,
+}
+
+impl WthorGame {
+    /**
+     * A game for writing to a WTHOR file, scored from its final position.  There is no solver
+     * here, so the theoretical score is set to the actual score.
+     */
+    pub fn from_moves(tournament: u16, black: u16, white: u16, moves: Vec<Move>) -> WthorGame {
+        let position: GameRepr<BitBoardBoard> = moves.iter().fold(GameRepr::new(), |game, mov| game.apply(*mov));
+        let score = wthor_score(position.scores());
+        WthorGame { tournament, black, white, score, theoretical_score: score, moves }
+    }
+
+    /**
+     * The position after all the moves have been played.
+     */
+    pub fn position<B: Board>(&self) -> GameRepr<B> {
+        self.moves.iter().fold(GameRepr::new(), |game, mov| game.apply(*mov))
+    }
+
+    pub fn black_name<'a>(&self, players: &'a [String]) -> Option<&'a str> {
+        players.get(self.black as usize).map(String::as_str)
+    }
+
+    pub fn white_name<'a>(&self, players: &'a [String]) -> Option<&'a str> {
+        players.get(self.white as usize).map(String::as_str)
+    }
+
+    pub fn tournament_name<'a>(&self, tournaments: &'a [String]) -> Option<&'a str> {
+        tournaments.get(self.tournament as usize).map(String::as_str)
+    }
+
+    fn from_record(record: &[u8; GAME_RECORD_LEN], game_number: usize) -> Result<WthorGame, WthorError> {
+        let mut game: GameRepr<BitBoardBoard> = GameRepr::new();
+        let mut moves = Vec::with_capacity(MAX_MOVES + 2);
+
+        for (i, &value) in record[8..].iter().enumerate().take_while(|(_, &value)| value != 0) {
+            let (row, col) = ((value / 10) as Pos - 1, (value % 10) as Pos - 1);
+            if !(0..8).contains(&row) || !(0..8).contains(&col) {
+                return Err(WthorError::InvalidMove { game_number, move_number: i + 1, value });
+            }
+
+            let mov = Move { player: game.next_turn, row, col };
+            play_recorded_move(&mut game, &mut moves, mov, None)
+                .map_err(|_| WthorError::IllegalMove { game_number, move_number: i + 1, mov })?;
+        }
+
+        Ok(WthorGame {
+            tournament: u16::from_le_bytes([record[0], record[1]]),
+            black: u16::from_le_bytes([record[2], record[3]]),
+            white: u16::from_le_bytes([record[4], record[5]]),
+            score: record[6],
+            theoretical_score: record[7],
+            moves,
+        })
+    }
+
+    fn to_record(&self) -> [u8; GAME_RECORD_LEN] {
+        let mut record = [0; GAME_RECORD_LEN];
+        record[0..2].copy_from_slice(&self.tournament.to_le_bytes());
+        record[2..4].copy_from_slice(&self.black.to_le_bytes());
+        record[4..6].copy_from_slice(&self.white.to_le_bytes());
+        record[6] = self.score;
+        record[7] = self.theoretical_score;
+        let squares = self.moves.iter()
+            .filter(|mov| !mov.is_pass())
+            .map(|mov| ((mov.row + 1) * 10 + mov.col + 1) as u8);
+        for (byte, square) in record[8..].iter_mut().zip(squares) {
+            *byte = square;
+        }
+        record
+    }
+}
+
+/**
+ * Black's disc count as WTHOR records it, with any empty squares going to the winner.
+ */
+fn wthor_score((black, white): (Score, Score)) -> u8 {
+    let empty = 64 - black - white;
+    let score = match black.cmp(&white) {
+        std::cmp::Ordering::Greater => black + empty,
+        std::cmp::Ordering::Less => black,
+        std::cmp::Ordering::Equal => black + empty / 2,
+    };
+    score as u8
+}
+
+/**
+ * The games in a WTHOR game file, read one at a time.
+ */
+pub struct WthorGames<R: Read> {
+    reader: R,
+    header: WthorHeader,
+    games_read: usize,
+}
+
+impl<R: Read> WthorGames<R> {
+    /**
+     * Read the header of a game file, ready to read its games.
+     */
+    pub fn new(mut reader: R) -> Result<WthorGames<R>, WthorError> {
+        let header = WthorHeader::read(&mut reader)?;
+        if header.board_size != 8 {
+            return Err(WthorError::UnsupportedBoardSize(header.board_size));
+        }
+        Ok(WthorGames { reader, header, games_read: 0 })
+    }
+
+    pub fn header(&self) -> &WthorHeader {
+        &self.header
+    }
+}
+
+impl<R: Read> Iterator for WthorGames<R> {
+    type Item = Result<WthorGame, WthorError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.games_read >= self.header.num_games as usize {
+            return None;
+        }
+        self.games_read += 1;
+
+        let mut record = [0; GAME_RECORD_LEN];
+        if let Err(err) = self.reader.read_exact(&mut record) {
+            /* Don't keep reading after the end of a truncated file */
+            self.games_read = self.header.num_games as usize;
+            return Some(Err(err.into()));
+        }
+        Some(WthorGame::from_record(&record, self.games_read))
+    }
+}
+
+/**
+ * Write a game file.  The number of games in the header is set from `games`.
+ */
+pub fn write_games(writer: &mut impl Write, header: &WthorHeader, games: &[WthorGame]) -> io::Result<()> {
+    let header = WthorHeader { num_games: games.len() as u32, num_names: 0, board_size: 8, ..header.clone() };
+    header.write(writer)?;
+    for game in games {
+        writer.write_all(&game.to_record())?;
+    }
+    Ok(())
+}
+
+/**
+ * Read a player or tournament file, whose records are `record_len` bytes long.
+ */
+pub fn read_names(reader: &mut impl Read, record_len: usize) -> Result<(WthorHeader, Vec<String>), WthorError> {
+    let header = WthorHeader::read(reader)?;
+    let mut record = vec![0; record_len];
+    let mut names = Vec::with_capacity(header.num_names as usize);
+    for _ in 0..header.num_names {
+        reader.read_exact(&mut record)?;
+        let len = record.iter().position(|&byte| byte == 0).unwrap_or(record_len);
+        names.push(record[..len].iter().map(|&byte| byte as char).collect());
+    }
+    Ok((header, names))
+}
+
+/**
+ * Read a player file (.jou).
+ */
+pub fn read_players(reader: &mut impl Read) -> Result<Vec<String>, WthorError> {
+    read_names(reader, PLAYER_RECORD_LEN).map(|(_, names)| names)
+}
+
+/**
+ * Read a tournament file (.trn).
+ */
+pub fn read_tournaments(reader: &mut impl Read) -> Result<Vec<String>, WthorError> {
+    read_names(reader, TOURNAMENT_RECORD_LEN).map(|(_, names)| names)
+}
+
+/**
+ * Write a player or tournament file, whose records are `record_len` bytes long.  Names are cut
+ * short to leave room for a terminating zero byte, and characters outside Latin-1 are written as
+ * "?".
+ */
+pub fn write_names(writer: &mut impl Write, header: &WthorHeader, names: &[String], record_len: usize) -> io::Result<()> {
+    let header = WthorHeader { num_games: 0, num_names: names.len() as u16, ..header.clone() };
+    header.write(writer)?;
+    for name in names {
+        let mut record = vec![0; record_len];
+        for (byte, ch) in record[..record_len - 1].iter_mut().zip(name.chars()) {
+            *byte = u8::try_from(ch).unwrap_or(b'?');
+        }
+        writer.write_all(&record)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Colour;
+    use super::*;
+
+    #[test]
+    fn test_wthor_score() {
+        assert_eq!(40, wthor_score((40, 24)));
+        assert_eq!(44, wthor_score((40, 20)));
+        assert_eq!(20, wthor_score((20, 40)));
+        assert_eq!(32, wthor_score((30, 30)));
+    }
+
+    #[test]
+    fn test_record() {
+        let moves = vec![Move { player: Colour::Black, row: 4, col: 5 }, Move { player: Colour::White, row: 5, col: 3 }];
+        let game = WthorGame::from_moves(1, 2, 3, moves);
+        let record = game.to_record();
+        assert_eq!([1, 0, 2, 0, 3, 0], record[..6]);
+        assert_eq!([56, 64, 0], record[8..11]);
+        assert_eq!(game, WthorGame::from_record(&record, 1).unwrap());
+    }
+}
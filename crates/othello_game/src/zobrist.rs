@@ -0,0 +1,140 @@
+/*!
+ * Zobrist keys, as described at https://www.chessprogramming.org/Zobrist_Hashing.
+ *
+ * A position's key is the XOR of one key per occupied square (depending on the colour of the piece
+ * on it), and another key if White is to move.  The keys are generated at compile time from a fixed
+ * seed, so a position has the same key in every build and on every platform.
+ *
+ * The keys for the 64 squares of an 8x8 board come first, so that they stay the same whatever
+ * other board sizes are supported.  Smaller boards use the keys for their squares in the top left
+ * corner, and larger boards use further keys for the squares beyond the eighth row and column.
+ */
+
+use crate::{Colour, Pos, MAX_BOARD_SIZE};
+
+const NUM_STANDARD_SQUARES: usize = 64;
+
+const NUM_SQUARES: usize = (MAX_BOARD_SIZE as usize) * (MAX_BOARD_SIZE as usize);
+
+const SEED: u64 = 0x4F74_6865_6C6C_6F21;
+
+/**
+ * One step of the SplitMix64 generator, returning the new state and the generated value.
+ */
+const fn splitmix64(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (state, z ^ (z >> 31))
+}
+
+const fn generate_keys() -> ([[u64; NUM_SQUARES]; 2], u64) {
+    let mut keys = [[0; NUM_SQUARES]; 2];
+    let mut state = SEED;
+    let mut colour = 0;
+    while colour < 2 {
+        let mut square = 0;
+        while square < NUM_STANDARD_SQUARES {
+            let (new_state, key) = splitmix64(state);
+            state = new_state;
+            keys[colour][square] = key;
+            square += 1;
+        }
+        colour += 1;
+    }
+    let (new_state, side_key) = splitmix64(state);
+    state = new_state;
+
+    let mut colour = 0;
+    while colour < 2 {
+        let mut square = NUM_STANDARD_SQUARES;
+        while square < NUM_SQUARES {
+            let (new_state, key) = splitmix64(state);
+            state = new_state;
+            keys[colour][square] = key;
+            square += 1;
+        }
+        colour += 1;
+    }
+    (keys, side_key)
+}
+
+/**
+ * The index of a square's keys: its bit position for squares on an 8x8 board, then the two extra
+ * rows, then the two extra columns of the other rows.
+ */
+const fn key_index(row: Pos, col: Pos) -> usize {
+    let (row, col) = (row as usize, col as usize);
+    let size = MAX_BOARD_SIZE as usize;
+    if row < 8 && col < 8 {
+        row * 8 + col
+    } else if row >= 8 {
+        NUM_STANDARD_SQUARES + (row - 8) * size + col
+    } else {
+        NUM_STANDARD_SQUARES + (size - 8) * size + row * (size - 8) + (col - 8)
+    }
+}
+
+const KEYS: ([[u64; NUM_SQUARES]; 2], u64) = generate_keys();
+
+/**
+ * The key to toggle when it is White's turn to move.
+ */
+pub const SIDE_KEY: u64 = KEYS.1;
+
+/**
+ * The key for a piece of the given colour on the given square of an 8x8 bitboard.
+ */
+#[inline(always)]
+pub(crate) fn square_key_at(colour: Colour, square: usize) -> u64 {
+    KEYS.0[colour as usize][square]
+}
+
+/**
+ * The key for a piece of the given colour on the given square.
+ */
+#[inline(always)]
+pub fn square_key(colour: Colour, row: Pos, col: Pos) -> u64 {
+    square_key_at(colour, key_index(row, col))
+}
+
+/**
+ * The change in key when the piece on the given square of an 8x8 bitboard is flipped to the other
+ * colour.
+ */
+#[inline(always)]
+pub(crate) fn flip_key_at(square: usize) -> u64 {
+    KEYS.0[0][square] ^ KEYS.0[1][square]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_keys_distinct() {
+        let mut all_keys: Vec<u64> = KEYS.0.iter().flatten().copied().collect();
+        all_keys.push(SIDE_KEY);
+        all_keys.sort();
+        all_keys.dedup();
+        assert_eq!(2 * NUM_SQUARES + 1, all_keys.len());
+        assert!(!all_keys.contains(&0));
+    }
+
+    #[test]
+    fn test_keys_stable() {
+        /* Keys are stored in opening books and datasets, so must never change */
+        assert_eq!(0x6870_D580_C15D_330A, square_key(Colour::Black, 0, 0));
+    }
+
+    #[test]
+    fn test_key_index() {
+        let mut indexes: Vec<usize> = (0..MAX_BOARD_SIZE)
+            .flat_map(|row| (0..MAX_BOARD_SIZE).map(move |col| key_index(row, col)))
+            .collect();
+        indexes.sort();
+        assert_eq!((0..NUM_SQUARES).collect::<Vec<_>>(), indexes);
+        assert_eq!(63, key_index(7, 7));
+    }
+}
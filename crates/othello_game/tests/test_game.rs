@@ -1,16 +1,17 @@
 use std::fmt::Write;
 
-use othello_game::{Colour, Game, GameRepr, Move};
+use othello_game::{Colour, Game, GameParseError, GameParseErrorKind, GameRepr, Move};
+use othello_game::default::DefaultBoard;
 
 #[test]
 fn test_initial_layout() {
     let game: GameRepr = GameRepr::new();
 
     assert_eq!(None, game.get_piece(0, 0));
-    assert_eq!(Some(Colour::Black), game.get_piece(3, 3));
-    assert_eq!(Some(Colour::White), game.get_piece(3, 4));
-    assert_eq!(Some(Colour::White), game.get_piece(4, 3));
-    assert_eq!(Some(Colour::Black), game.get_piece(4, 4));
+    assert_eq!(Some(Colour::White), game.get_piece(3, 3));
+    assert_eq!(Some(Colour::Black), game.get_piece(3, 4));
+    assert_eq!(Some(Colour::Black), game.get_piece(4, 3));
+    assert_eq!(Some(Colour::White), game.get_piece(4, 4));
 
     assert_eq!(Colour::Black, game.next_turn);
 }
@@ -23,8 +24,8 @@ fn test_debug() {
     write!(&mut str, "{:?}", game).unwrap();
 
     assert_eq!("········\n········\n········\n\
-        ···○●···\n\
         ···●○···\n\
+        ···○●···\n\
         ········\n········\n········\n", str);
 }
 
@@ -50,16 +51,66 @@ fn test_from_string() {
 
 #[test]
 fn test_from_bad_string() {
-    use othello_game::GameParseError::*;
+    fn error(line: usize, column: usize, kind: GameParseErrorKind) -> Result<GameRepr, GameParseError> {
+        Err(GameParseError { line, column, kind })
+    }
 
     let res: Result<GameRepr, _> = "●●●●●●●●○".try_into();
-    assert_eq!(Err(TooManyColumns), res);
+    assert_eq!(error(1, 9, GameParseErrorKind::TooManyColumns), res);
 
     let res: Result<GameRepr, _> = "●\n●\n●\n●\n●\n●\n●\n●\n○".try_into();
-    assert_eq!(Err(TooManyRows), res);
+    assert_eq!(error(9, 1, GameParseErrorKind::TooManyRows), res);
 
     let res: Result<GameRepr, _> = "qwerty".try_into();
-    assert_eq!(Err(InvalidPiece), res);
+    assert_eq!(error(1, 1, GameParseErrorKind::InvalidPiece('q')), res);
+
+    let res: Result<GameRepr, _> = "---\n- X ? O".parse();
+    assert_eq!(error(2, 5, GameParseErrorKind::InvalidPiece('?')), res);
+    assert_eq!("line 2, column 5: invalid piece '?'", res.unwrap_err().to_string());
+
+    let res: Result<GameRepr, _> = "1 ---\n3 -X-".parse();
+    assert_eq!(error(2, 1, GameParseErrorKind::WrongRowNumber { expected: 2, found: "3".to_string() }), res);
+}
+
+#[test]
+fn test_parse_dialects() {
+    let expected: GameRepr = GameRepr::new();
+    let diagrams = [
+        "········\n········\n········\n···●○···\n···○●···\n········\n········\n········\n",
+        "--------\n--------\n--------\n---OX---\n---XO---\n--------\n--------\n--------\nX to move",
+        ". . . . . . . .\n\n. . . . . . . .\n. . . o x . . .\n. . . x o\n",
+        "  a b c d e f g h
+         1 - - - - - - - - 1
+         2 - - - - - - - - 2
+         3 - - - - - - - - 3
+         4 - - - O X - - - 4
+         5 - - - X O - - - 5
+         6 - - - - - - - - 6
+         7 - - - - - - - - 7
+         8 - - - - - - - - 8
+           A B C D E F G H
+         Black to move",
+    ];
+    for diagram in diagrams {
+        let game: GameRepr = diagram.parse().expect("valid diagram");
+        assert!(game == expected, "{diagram}");
+    }
+}
+
+#[test]
+fn test_parse_side_to_move() {
+    let game: GameRepr = "---OX---\nWhite to move".parse().unwrap();
+    assert_eq!(Colour::White, game.next_turn);
+    assert_eq!(Some(Colour::White), game.get_piece(0, 3));
+
+    let game: GameRepr = "●○·\no".parse().unwrap();
+    assert_eq!(Colour::Black, game.next_turn);
+    assert_eq!(Some(Colour::White), game.get_piece(1, 0));
+
+    let board = "x".repeat(8) + "\n";
+    let game: GameRepr = (board.repeat(8) + "o").parse().unwrap();
+    assert_eq!(Colour::White, game.next_turn);
+    assert_eq!(Some(Colour::Black), game.get_piece(7, 7));
 }
 
 #[test]
@@ -68,10 +119,10 @@ fn test_initial_moves() {
 
     let mut moves = game.valid_moves(Colour::Black).into_iter();
 
-    assert_eq!(Some(Move { player: Colour::Black, row: 2, col: 4 }), moves.next());
-    assert_eq!(Some(Move { player: Colour::Black, row: 3, col: 5 }), moves.next());
-    assert_eq!(Some(Move { player: Colour::Black, row: 4, col: 2 }), moves.next());
-    assert_eq!(Some(Move { player: Colour::Black, row: 5, col: 3 }), moves.next());
+    assert_eq!(Some(Move { player: Colour::Black, row: 2, col: 3 }), moves.next());
+    assert_eq!(Some(Move { player: Colour::Black, row: 3, col: 2 }), moves.next());
+    assert_eq!(Some(Move { player: Colour::Black, row: 4, col: 5 }), moves.next());
+    assert_eq!(Some(Move { player: Colour::Black, row: 5, col: 4 }), moves.next());
     assert_eq!(None, moves.next());
 }
 
@@ -82,7 +133,7 @@ fn test_apply_move() {
     let mov = Move {
         player: Colour::Black,
         row: 2,
-        col: 4,
+        col: 3,
     };
 
     let game2 = game.apply(mov);
@@ -92,9 +143,9 @@ fn test_apply_move() {
     write!(&mut str, "{:?}", game2).unwrap();
 
     assert_eq!("········\n········\n\
-        ····○···\n\
+        ···○····\n\
         ···○○···\n\
-        ···●○···\n\
+        ···○●···\n\
         ········\n········\n········\n", str);
 }
 
@@ -105,3 +156,143 @@ fn test_no_moves() {
     let mut moves = game.valid_moves(Colour::Black).into_iter();
     assert_eq!(None, moves.next());
 }
+
+#[test]
+fn test_pass() {
+    let game: GameRepr = "●○·".try_into().unwrap();
+    assert_eq!(Colour::Black, game.next_turn);
+
+    assert!(game.must_pass());
+    assert!(!game.is_game_over());
+    assert!(game.is_valid_move(Move::pass(Colour::Black)));
+    assert!(!game.is_valid_move(Move::pass(Colour::White)));
+
+    let game2 = game.apply(Move::pass(Colour::Black));
+    assert_eq!(Colour::White, game2.next_turn);
+    assert!(game.board == game2.board);
+    assert!(!game2.must_pass());
+
+    /* Black still has no move, but it isn't their turn */
+    assert!(!game2.is_valid_move(Move::pass(Colour::Black)));
+
+    let game3 = game2.apply(Move { player: Colour::White, row: 0, col: 2 });
+    assert!(game3.is_game_over());
+    assert!(!game3.must_pass());
+    assert!(!game3.is_valid_move(Move::pass(Colour::Black)));
+    assert!(!game3.is_valid_move(Move::pass(Colour::White)));
+    assert_eq!((0, 3), game3.scores());
+}
+
+#[test]
+fn test_game_over() {
+    let game: GameRepr = GameRepr::new();
+    assert!(!game.is_game_over());
+
+    let game: GameRepr = "○●●●●●●●\n".try_into().unwrap();
+    assert!(game.is_game_over());
+
+    assert_eq!("PA", Move::pass(Colour::White).to_string());
+}
+
+#[test]
+fn test_parse_move() {
+    use othello_game::notation::MoveParseError;
+
+    assert_eq!(Ok(Move { player: Colour::White, row: 3, col: 2 }), Move::parse("c4", Colour::White));
+    assert_eq!(Ok(Move { player: Colour::White, row: 3, col: 2 }), Move::parse("C4", Colour::White));
+    assert_eq!(Ok(Move::pass(Colour::Black)), Move::parse("PA", Colour::Black));
+    assert_eq!(Ok(Move::pass(Colour::Black)), Move::parse("pass", Colour::Black));
+
+    assert_eq!(Ok(Move { player: Colour::Black, row: 4, col: 5 }), Move::parse("F5", Colour::Black));
+    assert_eq!(Ok(Move { player: Colour::Black, row: 5, col: 3 }), Move::parse("d6", Colour::Black));
+
+    /* Moves are parsed for boards up to 10x10, and checked against the board when played */
+    assert_eq!(Ok(Move { player: Colour::Black, row: 9, col: 9 }), Move::parse("J10", Colour::Black));
+    assert_eq!(Err(MoveParseError::InvalidColumn('k')), Move::parse("k4", Colour::Black));
+    assert_eq!(Err(MoveParseError::InvalidRow('1')), Move::parse("a11", Colour::Black));
+    assert_eq!(Err(MoveParseError::InvalidRow('0')), Move::parse("a0", Colour::Black));
+    assert_eq!(Err(MoveParseError::TrailingCharacters("x".to_string())), Move::parse("a1x", Colour::Black));
+    assert!(!GameRepr::<DefaultBoard>::new().is_valid_move(Move::parse("i4", Colour::Black).unwrap()));
+    assert_eq!(Err(MoveParseError::Empty), Move::parse("", Colour::Black));
+
+    for mov in GameRepr::<DefaultBoard>::new().valid_moves(Colour::Black) {
+        assert_eq!(Ok(mov), Move::parse(&mov.to_string(), mov.player));
+    }
+}
+
+#[test]
+fn test_parse_transcript() {
+    use othello_game::notation::{parse_transcript, write_transcript, TranscriptErrorKind};
+
+    let (game, moves): (GameRepr, _) = parse_transcript("f5d6c3d3c4").expect("legal transcript");
+    assert_eq!(5, moves.len());
+    assert_eq!(Colour::White, game.next_turn);
+    assert_eq!(Move { player: Colour::White, row: 5, col: 3 }, moves[1]);
+    assert_eq!("f5d6c3d3c4", write_transcript(&moves));
+
+    let (game2, _): (GameRepr, _) = parse_transcript("F5 D6, C3 d3\nc4").expect("legal transcript");
+    assert!(game == game2);
+
+    let err = parse_transcript::<DefaultBoard>("f5d6c3c3").expect_err("illegal move");
+    assert_eq!(4, err.move_number);
+    assert_eq!("c3", err.text);
+    assert_eq!(TranscriptErrorKind::IllegalMove, err.kind);
+    assert_eq!("move 4 \"c3\": illegal move", err.to_string());
+
+    let err = parse_transcript::<DefaultBoard>("f5z9").expect_err("invalid move");
+    assert_eq!("move 2 \"z9\": invalid column 'z'", err.to_string());
+}
+
+#[test]
+fn test_transcript_passes() {
+    use othello_game::notation::{replay_transcript, write_transcript};
+
+    let game: GameRepr = "●○·○".try_into().unwrap();
+
+    /* Black must pass before White can play c1 */
+    let (game2, moves) = replay_transcript(&game, "c1").expect("implicit pass");
+    assert_eq!(vec![Move::pass(Colour::Black), Move { player: Colour::White, row: 0, col: 2 }], moves);
+    assert_eq!("PAc1", write_transcript(&moves));
+
+    let (game3, _) = replay_transcript(&game, "PA c1").expect("explicit pass");
+    assert!(game2 == game3);
+}
+
+#[test]
+fn test_zobrist_key() {
+    use rand::seq::SliceRandom;
+    use othello_game::bitboardgame::BitBoardBoard;
+    use othello_game::{convert_board, Board};
+
+    fn fresh_key<B: Board>(board: &impl Board) -> u64 {
+        convert_board::<_, B>(board).zobrist_key()
+    }
+
+    let mut game: GameRepr = GameRepr::new();
+    let mut bb_game: GameRepr<BitBoardBoard> = GameRepr::new();
+    assert_eq!(game.zobrist_key(), bb_game.zobrist_key());
+    assert_ne!(game.zobrist_key(), game.pass().zobrist_key());
+
+    while !game.is_game_over() {
+        let moves = game.valid_moves(game.next_turn);
+        let mov = moves.choose(&mut rand::thread_rng()).copied()
+            .unwrap_or(Move::pass(game.next_turn));
+        game = game.apply(mov);
+        bb_game = bb_game.apply(mov);
+
+        assert_eq!(game.zobrist_key(), bb_game.zobrist_key());
+        assert_eq!(fresh_key::<DefaultBoard>(&game.board), game.board.zobrist_key());
+        assert_eq!(fresh_key::<BitBoardBoard>(&bb_game.board), bb_game.board.zobrist_key());
+    }
+}
+
+#[test]
+fn test_zobrist_transposition() {
+    use othello_game::notation::parse_transcript;
+
+    /* The same position reached by different move orders */
+    let (game1, _): (GameRepr, _) = parse_transcript("d3c3c4e3").unwrap();
+    let (game2, _): (GameRepr, _) = parse_transcript("c4c3d3e3").unwrap();
+    assert!(game1 == game2);
+    assert_eq!(game1.zobrist_key(), game2.zobrist_key());
+}
@@ -0,0 +1,130 @@
+use othello_game::bitboardgame::BitBoardBoard;
+use othello_game::default::DefaultBoard;
+use othello_game::ggf::{ggf_games, parse_ggf, write_ggf, GgfEnding, GgfError, GgfErrorKind, GgfGame, GgfMove};
+use othello_game::notation::{parse_transcript, TranscriptErrorKind};
+use othello_game::{Colour, Game, GameRepr, Move};
+
+const START_BOARD: &str = "BO[8 -------- -------- -------- ---O*--- ---*O--- -------- -------- -------- *]";
+
+fn ggf(tags: &str, moves: &str) -> String {
+    format!("(;GM[Othello]{tags}{START_BOARD}{moves};)")
+}
+
+#[test]
+fn test_parse_game() {
+    let text = ggf(
+        "PC[GGS/os]DT[2003.12.15_13:24:03.MST]PB[Saio]PW[Zebra]RB[2197.01]RW[2199.72]TI[15:00//02:00]TY[8]RE[-4.000:r]",
+        "B[f5//0.01]W[d6/-2.50/1:05.5]B[C3/1.00/]W[d3]",
+    );
+    let games: Vec<GgfGame> = parse_ggf(&text).expect("valid game");
+    assert_eq!(1, games.len());
+    let game = &games[0];
+
+    assert_eq!(Some("GGS/os"), game.header.place.as_deref());
+    assert_eq!(Some("2003.12.15_13:24:03.MST"), game.header.date.as_deref());
+    assert_eq!(Some("Saio"), game.header.black.as_deref());
+    assert_eq!(Some("Zebra"), game.header.white.as_deref());
+    assert_eq!(Some(2197.01), game.header.black_rating);
+    assert_eq!(Some(2199.72), game.header.white_rating);
+    assert_eq!(Some("15:00//02:00"), game.header.time_control.as_deref());
+    assert_eq!(Some("8"), game.header.game_type.as_deref());
+    let result = game.header.result.expect("result");
+    assert_eq!(-4.0, result.score);
+    assert_eq!(GgfEnding::Resigned, result.ending);
+
+    assert!(game.start == GameRepr::new());
+    assert_eq!(4, game.moves.len());
+    assert_eq!(GgfMove { mov: Move { player: Colour::Black, row: 4, col: 5 }, eval: None, time: Some(0.01) }, game.moves[0]);
+    assert_eq!(GgfMove { mov: Move { player: Colour::White, row: 5, col: 3 }, eval: Some(-2.5), time: Some(65.5) }, game.moves[1]);
+    assert_eq!(Some(1.0), game.moves[2].eval);
+
+    let (expected, moves): (GameRepr, _) = parse_transcript("f5d6c3d3").unwrap();
+    assert!(game.position() == expected);
+    assert_eq!(moves, game.all_moves());
+}
+
+#[test]
+fn test_round_trip() {
+    let (_, moves): (GameRepr<BitBoardBoard>, _) = parse_transcript("f5d6c3d3c4f4f6f3e6e7").unwrap();
+    let text = ggf(
+        "PB[Black \\] player]PW[White]RE[+12.000]CO[A comment]",
+        &moves.iter().enumerate()
+            .map(|(i, mov)| {
+                let tag = if mov.player == Colour::Black { "B" } else { "W" };
+                format!("{tag}[{}/{}.00/{}.50]", mov.to_string().to_ascii_lowercase(), i, i)
+            })
+            .collect::<String>(),
+    );
+
+    let game: GgfGame<BitBoardBoard> = parse_ggf(&text).expect("valid game").remove(0);
+    assert_eq!(Some("Black ] player"), game.header.black.as_deref());
+    assert_eq!(vec![("CO".to_string(), "A comment".to_string())], game.header.other);
+    assert_eq!(moves, game.all_moves());
+
+    let written = write_ggf(&game);
+    let game2: GgfGame<BitBoardBoard> = parse_ggf(&written).expect("valid game").remove(0);
+    assert_eq!(game.header, game2.header);
+    assert_eq!(game.moves, game2.moves);
+    assert!(game.start == game2.start);
+    assert_eq!(written, write_ggf(&game2));
+}
+
+#[test]
+fn test_passes() {
+    let start: GameRepr = "●○·○".try_into().unwrap();
+    let header = Default::default();
+    let game = GgfGame { header, start, moves: vec![] };
+    let board = write_ggf(&game);
+    assert!(board.contains("BO[8 O*-*---- -------- "));
+
+    /* Black has no moves, so must pass before White plays c1 */
+    let explicit = board.replace(";)", "B[PA]W[c1];)");
+    let implicit = board.replace(";)", "W[c1];)");
+    for text in [explicit, implicit] {
+        let game: GgfGame = parse_ggf(&text).expect("valid game").remove(0);
+        assert_eq!(vec![Move::pass(Colour::Black), Move { player: Colour::White, row: 0, col: 2 }], game.all_moves());
+        assert!(game.position().must_pass());
+        assert!(write_ggf(&game).contains("B[pa]W[c1]"));
+    }
+}
+
+#[test]
+fn test_many_games() {
+    let one = ggf("", "B[f5]W[d6]");
+    let text = format!("{one}\n{one}\n\n{}", ggf("", "B[f5]W[f5]"));
+    let games: Vec<_> = ggf_games::<DefaultBoard>(&text).collect();
+    assert_eq!(3, games.len());
+    assert!(games[0].is_ok() && games[1].is_ok());
+
+    let err = games[2].as_ref().expect_err("illegal move");
+    assert_eq!(3, err.game_number);
+    let GgfErrorKind::Move(move_err) = &err.kind else { panic!("expected a move error") };
+    assert_eq!(2, move_err.move_number);
+    assert_eq!(TranscriptErrorKind::IllegalMove, move_err.kind);
+    assert_eq!("game 3: move 2 \"f5\": illegal move", err.to_string());
+}
+
+#[test]
+fn test_errors() {
+    let error = |kind| Some(GgfError { game_number: 1, kind });
+
+    assert_eq!(error(GgfErrorKind::MissingStart), parse_ggf::<DefaultBoard>("GM[Othello]").err());
+    assert_eq!(error(GgfErrorKind::Unterminated), parse_ggf::<DefaultBoard>("(;GM[Othello]").err());
+    assert_eq!(error(GgfErrorKind::MissingBoard), parse_ggf::<DefaultBoard>("(;GM[Othello]B[f5];)").err());
+    assert_eq!(error(GgfErrorKind::UnsupportedGame("Chess".to_string())),
+               parse_ggf::<DefaultBoard>(&ggf("", "").replace("Othello", "Chess")).err());
+    assert_eq!(error(GgfErrorKind::WrongBoardSize { expected: 6, found: "8".to_string() }),
+               parse_ggf::<DefaultBoard<6>>(&ggf("", "")).err());
+    assert_eq!(error(GgfErrorKind::InvalidBoard("8".to_string())),
+               parse_ggf::<DefaultBoard>("(;GM[Othello]BO[8];)").err());
+    assert_eq!(error(GgfErrorKind::InvalidBoard("8 *".to_string())),
+               parse_ggf::<DefaultBoard>("(;GM[Othello]BO[8 *];)").err());
+    assert_eq!(error(GgfErrorKind::InvalidValue { tag: "RB".to_string(), value: "high".to_string() }),
+               parse_ggf::<DefaultBoard>(&ggf("RB[high]", "")).err());
+    assert_eq!(error(GgfErrorKind::InvalidValue { tag: "B".to_string(), value: "x".to_string() }),
+               parse_ggf::<DefaultBoard>(&ggf("", "B[f5/x]")).err());
+
+    /* White can't move first from the starting position */
+    let err = parse_ggf::<DefaultBoard>(&ggf("", "W[f5]")).unwrap_err();
+    assert_eq!("game 1: move 1 \"f5\": illegal move", err.to_string());
+}
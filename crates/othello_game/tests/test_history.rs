@@ -0,0 +1,79 @@
+use othello_game::{Colour, Game, GameRepr, Move};
+use othello_game::history::GameHistory;
+use othello_game::notation::parse_transcript;
+
+#[test]
+fn test_undo_redo() {
+    let mut history: GameHistory = GameHistory::new();
+    assert!(!history.can_undo());
+    assert_eq!(None, history.undo());
+
+    let (expected, moves): (GameRepr, _) = parse_transcript("f5d6c3").unwrap();
+    for mov in &moves {
+        history.apply_in_place(*mov);
+    }
+    assert_eq!(3, history.ply());
+    assert_eq!(&moves[..], history.moves());
+    assert!(expected == *history.current());
+
+    assert_eq!(Some(moves[2]), history.undo());
+    assert_eq!(Some(moves[1]), history.undo());
+    assert_eq!(1, history.ply());
+    assert_eq!(&moves[..1], history.moves());
+    assert_eq!(Colour::White, history.next_turn());
+
+    assert_eq!(Some(moves[1]), history.redo());
+    assert_eq!(Some(moves[2]), history.redo());
+    assert_eq!(None, history.redo());
+    assert!(expected == *history.current());
+}
+
+#[test]
+fn test_play_discards_redo() {
+    let mut history: GameHistory = GameHistory::new();
+    let (_, moves): (GameRepr, _) = parse_transcript("f5d6c3").unwrap();
+    moves.iter().for_each(|mov| history.play(*mov));
+
+    history.undo();
+    let other = history.valid_moves(Colour::Black).into_iter()
+        .find(|mov| *mov != moves[2])
+        .unwrap();
+    history.play(other);
+    assert_eq!(3, history.all_moves().len());
+    assert_eq!(other, history.moves()[2]);
+    assert!(!history.can_redo());
+}
+
+#[test]
+fn test_go_to() {
+    let mut history: GameHistory = GameHistory::new();
+    let (_, moves): (GameRepr, _) = parse_transcript("f5d6c3d3c4").unwrap();
+    moves.iter().for_each(|mov| history.play(*mov));
+
+    assert!(history.go_to(0));
+    assert!(*history.start() == *history.current());
+    assert_eq!(5, history.all_moves().len());
+
+    assert!(history.go_to(2));
+    let (expected, _): (GameRepr, _) = parse_transcript("f5d6").unwrap();
+    assert!(expected == *history.current());
+
+    assert!(!history.go_to(6));
+    assert_eq!(2, history.ply());
+}
+
+#[test]
+fn test_history_passes() {
+    let start: GameRepr = "●○·○".try_into().unwrap();
+    let mut history = GameHistory::from_position(start);
+
+    assert!(history.must_pass());
+    history.play(Move::pass(Colour::Black));
+    history.play(Move { player: Colour::White, row: 0, col: 2 });
+    assert_eq!(vec![Move::pass(Colour::Black), Move { player: Colour::White, row: 0, col: 2 }], history.moves());
+
+    assert_eq!(Some(Move { player: Colour::White, row: 0, col: 2 }), history.undo());
+    assert_eq!(Colour::White, history.next_turn());
+    assert_eq!(Some(Move::pass(Colour::Black)), history.undo());
+    assert_eq!(Colour::Black, history.next_turn());
+}
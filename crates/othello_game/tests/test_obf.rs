@@ -0,0 +1,88 @@
+use othello_game::bitboardgame::BitBoardBoard;
+use othello_game::default::DefaultBoard;
+use othello_game::notation::parse_transcript;
+use othello_game::obf::{parse_obf, parse_obf_file, parse_position, write_obf, write_position, ObfError, ObfErrorKind, ObfPosition};
+use othello_game::{Colour, Game, GameRepr, Move};
+
+const START: &str = "---------------------------OX------XO--------------------------- X";
+
+#[test]
+fn test_write_position() {
+    let game: GameRepr = GameRepr::new();
+    assert_eq!(START, write_position(&game));
+
+    let (game, _): (GameRepr, _) = parse_transcript("f5").unwrap();
+    assert_eq!("---------------------------OX------XXX-------------------------- O", write_position(&game));
+
+    let game: GameRepr<DefaultBoard<4>> = GameRepr::new();
+    assert_eq!("-----OX--XO----- X", write_position(&game));
+}
+
+#[test]
+fn test_parse_position() {
+    let game: GameRepr<BitBoardBoard> = parse_position(START).expect("valid position");
+    assert!(game == GameRepr::new());
+
+    let (expected, _): (GameRepr<BitBoardBoard>, _) = parse_transcript("f5d6c3").unwrap();
+    let text = write_position(&expected);
+    let game: GameRepr<BitBoardBoard> = parse_position(&text).expect("valid position");
+    assert!(game == expected);
+    assert_eq!(Colour::White, game.next_turn);
+
+    /* Other common spellings of the pieces and side */
+    let lower = text.replace('X', "*").replace('O', "o").replace('-', ".");
+    let lower = lower.replace(" o", " White to move");
+    assert!(parse_position::<BitBoardBoard>(&lower).expect("valid position") == expected);
+}
+
+#[test]
+fn test_parse_annotations() {
+    let (game, _): (GameRepr, _) = parse_transcript("f5").unwrap();
+    let line = format!("{}; d6:+4; F4:-2; f6:0;", write_position(&game));
+    let position: ObfPosition<DefaultBoard> = parse_obf(&line).expect("valid position");
+
+    assert!(position.game == game);
+    let d6 = Move { player: Colour::White, row: 5, col: 3 };
+    assert_eq!(Some((d6, 4)), position.best_move());
+    assert_eq!(3, position.moves.len());
+    for (mov, _) in &position.moves {
+        assert!(game.is_valid_move(*mov));
+    }
+
+    assert_eq!(format!("{}; D6:+4; F4:-2; F6:+0;", write_position(&game)), write_obf(&position));
+}
+
+#[test]
+fn test_parse_file() {
+    let (game, _): (GameRepr, _) = parse_transcript("f5d6").unwrap();
+    let text = format!("% Some positions\n{START}; F5:+0;\n\n{}; c3:+2;\n", write_position(&game));
+    let positions: Vec<ObfPosition<DefaultBoard>> = parse_obf_file(&text).expect("valid file");
+    assert_eq!(2, positions.len());
+    assert!(positions[1].game == game);
+
+    /* FFO files give the side to move on its own line */
+    let board = &START[..64];
+    let positions: Vec<ObfPosition<DefaultBoard>> = parse_obf_file(&format!("{board}\nBlack to move\n")).expect("valid file");
+    assert_eq!(1, positions.len());
+    assert!(positions[0].game == GameRepr::new());
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = |line_number, kind| Some(ObfError { line_number, kind });
+
+    assert_eq!(error(1, ObfErrorKind::Empty), parse_position::<DefaultBoard>(" ; F5:+0;").err());
+    assert_eq!(error(1, ObfErrorKind::WrongLength { expected: 64, found: 63 }), parse_position::<DefaultBoard>(&START[1..]).err());
+    assert_eq!(error(1, ObfErrorKind::WrongLength { expected: 36, found: 64 }), parse_position::<DefaultBoard<6>>(START).err());
+    assert_eq!(error(1, ObfErrorKind::InvalidPiece('?')), parse_position::<DefaultBoard>(&START.replacen('-', "?", 1)).err());
+    assert_eq!(error(1, ObfErrorKind::InvalidSide("Z".to_string())), parse_position::<DefaultBoard>(&START.replace(" X", " Z")).err());
+    assert_eq!(error(1, ObfErrorKind::InvalidSide("".to_string())), parse_position::<DefaultBoard>(&START[..64]).err());
+
+    let text = format!("{START}; F5:+0;\n{START}; F5:+x;");
+    assert_eq!(Some(ObfError { line_number: 2, kind: ObfErrorKind::InvalidScore("+x".to_string()) }),
+               parse_obf_file::<DefaultBoard>(&text).err());
+    assert_eq!("line 2: invalid score \"+x\"", parse_obf_file::<DefaultBoard>(&text).unwrap_err().to_string());
+
+    assert_eq!(error(1, ObfErrorKind::MissingScore), parse_obf::<DefaultBoard>(&format!("{START}; F5;")).err());
+    assert_eq!(error(1, ObfErrorKind::MissingScore), parse_obf::<DefaultBoard>(&format!("{START}; F5: ;")).err());
+}
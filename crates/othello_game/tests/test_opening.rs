@@ -0,0 +1,76 @@
+use othello_game::default::DefaultBoard;
+use othello_game::notation::parse_transcript;
+use othello_game::opening::{classify_opening, Openings, OPENINGS};
+use othello_game::symmetry::Transform;
+use othello_game::{GameRepr, Move};
+
+fn moves(transcript: &str) -> Vec<Move> {
+    let (_, moves): (GameRepr<DefaultBoard>, _) = parse_transcript(transcript).expect("legal transcript");
+    moves
+}
+
+#[test]
+fn test_openings_are_legal() {
+    for (name, transcript) in OPENINGS {
+        assert!(transcript.starts_with("f5"), "{name} starts with f5");
+        let result = classify_opening(&moves(transcript));
+        assert_eq!(Some(*name), result.name);
+        assert_eq!(transcript.len() / 2, result.opening_plies);
+    }
+    assert_eq!(OPENINGS.len(), Openings::standard().len());
+}
+
+#[test]
+fn test_deepest_match() {
+    let result = classify_opening(&moves("f5d6c3d3c4f4f6f3e6e7d7"));
+    assert_eq!(Some("No-Kung"), result.name);
+    assert_eq!(10, result.opening_plies);
+    assert_eq!(10, result.known_plies);
+
+    /* On the way to the No-Kung, but past the Tiger */
+    let result = classify_opening(&moves("f5d6c3d3c4f4f6"));
+    assert_eq!(Some("Tiger"), result.name);
+    assert_eq!(5, result.opening_plies);
+    assert_eq!(7, result.known_plies);
+
+    let result = classify_opening(&moves("f5d6c3d3c4b3"));
+    assert_eq!(Some("Tiger"), result.name);
+    assert_eq!(5, result.known_plies);
+
+    /* Past the Stephenson, on the way to the Comp'Oth */
+    let result = classify_opening(&moves("f5d6c3d3c4f4c5b3c2e6c6"));
+    assert_eq!(Some("Stephenson"), result.name);
+    assert_eq!(9, result.opening_plies);
+    assert_eq!(11, result.known_plies);
+
+    let result = classify_opening(&moves("f5d6c5f4e3c6d3f6e6d7g3"));
+    assert_eq!(Some("Rose"), result.name);
+    assert_eq!(10, result.opening_plies);
+}
+
+#[test]
+fn test_symmetry() {
+    let tiger = moves("f5d6c3d3c4");
+    for first in ["d3", "c4", "e6"] {
+        let transform = [Transform::Rotate180, Transform::FlipDiagonal, Transform::FlipAntiDiagonal].into_iter()
+            .find(|t| t.apply_move(tiger[0], 8).to_string().eq_ignore_ascii_case(first))
+            .unwrap();
+        let turned: Vec<Move> = tiger.iter().map(|mov| transform.apply_move(*mov, 8)).collect();
+        assert_eq!(Some("Tiger"), classify_opening(&turned).name, "starting with {first}");
+    }
+}
+
+#[test]
+fn test_unknown() {
+    let result = classify_opening(&[]);
+    assert_eq!(None, result.name);
+    assert_eq!(0, result.known_plies);
+
+    let mut openings = Openings::new();
+    assert!(openings.is_empty());
+    openings.add("Tiger from c4", "c4e3f6e6f5").unwrap();
+    assert!(openings.add("Illegal", "f5f5").is_err());
+    let result = openings.classify(&moves("f5d6c3d3c4f4"));
+    assert_eq!(Some("Tiger from c4"), result.name);
+    assert_eq!(None, openings.classify(&moves("f5f6")).name);
+}
@@ -0,0 +1,127 @@
+use rand::seq::SliceRandom;
+
+use othello_game::bitboardgame::BitBoardBoard;
+use othello_game::default::DefaultBoard;
+use othello_game::packed::{read_positions, write_positions, LabelledPosition, PackedError, PackedPosition,
+                           PositionFileHeader, PositionReader, PositionWriter, HEADER_LEN, MAGIC, PACKED_LEN};
+use othello_game::{Colour, Game, GameRepr, Move};
+
+fn random_positions() -> Vec<GameRepr<BitBoardBoard>> {
+    let mut game: GameRepr<BitBoardBoard> = GameRepr::new();
+    let mut positions = vec![game.clone()];
+    while !game.is_game_over() {
+        let mov = game.valid_moves(game.next_turn)
+            .choose(&mut rand::thread_rng()).copied()
+            .unwrap_or(Move::pass(game.next_turn));
+        game = game.apply(mov);
+        positions.push(game.clone());
+    }
+    positions
+}
+
+fn labelled(game: &GameRepr<BitBoardBoard>, i: usize) -> LabelledPosition {
+    let best_move = game.valid_moves(game.next_turn).first().copied()
+        .or(Some(Move::pass(game.next_turn)));
+    LabelledPosition {
+        position: PackedPosition::from_game(game),
+        score: Some(i as i8 - 30),
+        best_move: if i.is_multiple_of(7) { None } else { best_move },
+        result: if i.is_multiple_of(5) { None } else { Some(-(i as i8)) },
+    }
+}
+
+#[test]
+fn test_pack_position() {
+    let game: GameRepr<BitBoardBoard> = GameRepr::new();
+    let packed = PackedPosition::from_game(&game);
+    assert_eq!(game.board.blacks().bits(), packed.black);
+    assert_eq!(game.board.whites().bits(), packed.white);
+    assert_eq!(Colour::Black, packed.next_turn);
+
+    for game in random_positions() {
+        let packed = PackedPosition::from_game(&game);
+        let bytes = packed.to_bytes();
+        assert_eq!(PACKED_LEN, bytes.len());
+        assert_eq!(Some(packed), PackedPosition::from_bytes(&bytes, game.next_turn));
+
+        let unpacked: GameRepr<BitBoardBoard> = packed.to_game();
+        assert!(unpacked == game);
+        let unpacked: GameRepr<DefaultBoard> = packed.to_game();
+        assert_eq!(packed, PackedPosition::from_game(&unpacked));
+    }
+
+    let mut bytes = [0; PACKED_LEN];
+    bytes[0] = 1;
+    bytes[8] = 1;
+    assert_eq!(None, PackedPosition::from_bytes(&bytes, Colour::Black));
+}
+
+#[test]
+fn test_round_trip() {
+    let positions: Vec<LabelledPosition> = random_positions().iter().enumerate()
+        .map(|(i, game)| labelled(game, i))
+        .collect();
+
+    let header = PositionFileHeader::new(true, true, true);
+    let mut bytes = Vec::new();
+    write_positions(&mut bytes, header, &positions).unwrap();
+    assert_eq!(HEADER_LEN + positions.len() * (PACKED_LEN + 4), bytes.len());
+    assert_eq!(MAGIC, bytes[..4]);
+
+    let (read_header, read) = read_positions(&bytes[..]).unwrap();
+    assert_eq!(header, read_header);
+    assert_eq!(positions, read);
+}
+
+#[test]
+fn test_without_labels() {
+    let positions: Vec<LabelledPosition> = random_positions().iter().enumerate()
+        .map(|(i, game)| labelled(game, i))
+        .collect();
+
+    let header = PositionFileHeader::new(false, true, false);
+    let mut writer = PositionWriter::new(Vec::new(), header).unwrap();
+    for position in &positions {
+        writer.write(position).unwrap();
+    }
+    let bytes = writer.finish().unwrap();
+    assert_eq!(HEADER_LEN + positions.len() * (PACKED_LEN + 2), bytes.len());
+
+    let reader = PositionReader::new(&bytes[..]).unwrap();
+    assert_eq!(&header, reader.header());
+    for (position, read) in positions.iter().zip(reader) {
+        let read = read.unwrap();
+        assert_eq!(position.position, read.position);
+        assert_eq!(position.best_move, read.best_move);
+        assert_eq!(None, read.score);
+        assert_eq!(None, read.result);
+    }
+}
+
+#[test]
+fn test_errors() {
+    assert!(matches!(read_positions(&b"OTHELLO!"[..]), Err(PackedError::InvalidMagic(_))));
+    assert!(matches!(read_positions(&b"OTHP\x02\0\0\0"[..]), Err(PackedError::UnsupportedVersion(2))));
+    assert!(matches!(read_positions(&b"OTHP\x01\x08\0\0"[..]), Err(PackedError::UnknownFlags(8))));
+    assert!(matches!(read_positions(&b"OTH"[..]), Err(PackedError::Io(_))));
+
+    let game: GameRepr<BitBoardBoard> = GameRepr::new();
+    let mut bytes = Vec::new();
+    let header = PositionFileHeader::new(false, true, false);
+    write_positions(&mut bytes, header, &[labelled(&game, 1), labelled(&game, 2)]).unwrap();
+
+    let mut truncated = bytes.clone();
+    truncated.pop();
+    let read: Vec<_> = PositionReader::new(&truncated[..]).unwrap().collect();
+    assert_eq!(2, read.len());
+    assert!(read[0].is_ok());
+    assert!(matches!(read[1], Err(PackedError::Io(_))));
+
+    let mut bad_side = bytes.clone();
+    bad_side[HEADER_LEN + PACKED_LEN] = 2;
+    assert!(matches!(read_positions(&bad_side[..]), Err(PackedError::InvalidSide { position_number: 1, value: 2 })));
+
+    let mut bad_move = bytes.clone();
+    bad_move[HEADER_LEN + 2 * (PACKED_LEN + 2) - 1] = 65;
+    assert!(matches!(read_positions(&bad_move[..]), Err(PackedError::InvalidMove { position_number: 2, value: 65 })));
+}
@@ -0,0 +1,59 @@
+use othello_game::bitboardgame::BitBoardBoard;
+use othello_game::default::DefaultBoard;
+use othello_game::tableboard::TableBoard;
+use othello_game::perft::{perft, perft_divide, perft_parallel, STANDARD_PERFT};
+use othello_game::{Colour, GameRepr, Move};
+
+#[test]
+fn test_perft_bitboard() {
+    let game: GameRepr<BitBoardBoard> = GameRepr::new();
+    for depth in 1..=7 {
+        assert_eq!(STANDARD_PERFT[depth - 1], perft(&game, depth), "depth {depth}");
+    }
+}
+
+#[test]
+fn test_perft_default() {
+    let game: GameRepr<DefaultBoard> = GameRepr::new();
+    for depth in 1..=6 {
+        assert_eq!(STANDARD_PERFT[depth - 1], perft(&game, depth), "depth {depth}");
+    }
+}
+
+#[test]
+fn test_perft_table() {
+    let game: GameRepr<TableBoard> = GameRepr::new();
+    for depth in 1..=7 {
+        assert_eq!(STANDARD_PERFT[depth - 1], perft(&game, depth), "depth {depth}");
+    }
+}
+
+#[test]
+fn test_perft_parallel() {
+    let game: GameRepr<BitBoardBoard> = GameRepr::new();
+    for depth in 0..=7 {
+        assert_eq!(perft(&game, depth), perft_parallel(&game, depth, 4), "depth {depth}");
+    }
+}
+
+#[test]
+fn test_perft_divide() {
+    let game: GameRepr<BitBoardBoard> = GameRepr::new();
+    let divided = perft_divide(&game, 5);
+    assert_eq!(4, divided.len());
+    assert_eq!(STANDARD_PERFT[4], divided.iter().map(|(_, count)| count).sum::<u64>());
+
+    /* The four opening moves are symmetric */
+    assert!(divided.iter().all(|(_, count)| *count == STANDARD_PERFT[4] / 4));
+}
+
+#[test]
+fn test_perft_passes() {
+    /* Black must pass, then White plays the only move and the game ends */
+    let game: GameRepr<BitBoardBoard> = "●○·".try_into().unwrap();
+    assert_eq!(vec![(Move::pass(Colour::Black), 1)], perft_divide(&game, 2));
+    assert_eq!(1, perft(&game, 1));
+    assert_eq!(1, perft(&game, 2));
+    assert_eq!(1, perft(&game, 5));
+    assert_eq!(1, perft_parallel(&game, 5, 2));
+}
@@ -0,0 +1,158 @@
+use rand::seq::SliceRandom;
+
+use othello_game::bitboardgame::BitBoardBoard;
+use othello_game::notation::{parse_transcript, TranscriptErrorKind};
+use othello_game::obf::parse_position;
+use othello_game::record::{parse_record, parse_records, write_record, write_records, GameRecord, RecordErrorKind, RecordedMove, BLACK, BLACK_AI, DATE, RESULT, SCORE, WHITE};
+use othello_game::{Board, Colour, Game, GameRepr, Move};
+
+fn random_record<B: Board>() -> GameRecord<B> {
+    let mut record = GameRecord::new();
+    let mut game: GameRepr<B> = GameRepr::new();
+    while !game.is_game_over() {
+        let mov = *game.valid_moves(game.next_turn)
+            .choose(&mut rand::thread_rng())
+            .unwrap_or(&Move::pass(game.next_turn));
+        game = game.apply(mov);
+        record.push(RecordedMove { mov, eval: Some(1.5), time: Some(0.25), comment: None });
+    }
+    record.set_header(BLACK, "Black \"the first\"");
+    record.set_header(WHITE, "White");
+    record.set_result();
+    record
+}
+
+#[test]
+fn test_parse_record() {
+    let text = "[Event \"Test\"]
+[Black \"AlphaBetaAI\"]
+[White \"Random \\\"player\\\"\"]
+[Date \"2024.03.17\"]
+[Result \"*\"]
+
+1. f5 {[%eval +2.00] [%time 0.15] Best move} d6 {Perpendicular}
+2. C3 {[%time 1.5]} d3 *";
+
+    let record: GameRecord = parse_record(text).expect("valid record");
+    assert_eq!(Some("AlphaBetaAI"), record.header(BLACK));
+    assert_eq!(Some("Random \"player\""), record.header(WHITE));
+    assert_eq!(Some("2024.03.17"), record.header(DATE));
+    assert_eq!(None, record.header(BLACK_AI));
+
+    assert_eq!(4, record.moves.len());
+    assert_eq!(RecordedMove {
+        mov: Move { player: Colour::Black, row: 4, col: 5 },
+        eval: Some(2.0),
+        time: Some(0.15),
+        comment: Some("Best move".to_string()),
+    }, record.moves[0]);
+    assert_eq!(Some("Perpendicular"), record.moves[1].comment.as_deref());
+    assert_eq!(Some(1.5), record.moves[2].time);
+    assert_eq!(None, record.moves[3].comment);
+
+    let (expected, moves): (GameRepr, _) = parse_transcript("f5d6c3d3").unwrap();
+    assert!(record.position() == expected);
+    assert_eq!(moves, record.all_moves());
+}
+
+#[test]
+fn test_implicit_passes() {
+    let record: GameRecord<BitBoardBoard> = (0..100)
+        .map(|_| random_record())
+        .find(|record| record.moves.iter().any(|mov| mov.mov.is_pass()))
+        .expect("a game with a pass");
+    let text: String = record.moves.iter()
+        .filter(|mov| !mov.mov.is_pass())
+        .map(|mov| format!("{} ", mov.mov))
+        .collect();
+    let parsed: GameRecord<BitBoardBoard> = parse_record(&text).expect("valid record");
+    assert_eq!(record.all_moves(), parsed.all_moves());
+
+    let err = parse_record::<BitBoardBoard>("1. f5 PA").unwrap_err();
+    assert!(matches!(err.kind, RecordErrorKind::Move(_)));
+}
+
+#[test]
+fn test_round_trip() {
+    for _ in 0..20 {
+        let record: GameRecord<BitBoardBoard> = random_record();
+        let text = write_record(&record);
+        let parsed: GameRecord<BitBoardBoard> = parse_record(&text).expect("valid record");
+        assert_eq!(record.headers, parsed.headers);
+        assert_eq!(record.moves, parsed.moves);
+        assert!(parsed.position().is_game_over());
+        assert!(text.lines().all(|line| line.len() <= 80));
+        assert_eq!(text, write_record(&parsed));
+    }
+}
+
+#[test]
+fn test_comment_escapes() {
+    let mut record: GameRecord = GameRecord::new();
+    let comment = "Avoids {the} C-square \\ wall";
+    record.push(RecordedMove { mov: Move::parse("f5", Colour::Black).unwrap(), eval: None, time: None, comment: Some(comment.to_string()) });
+    let text = write_record(&record);
+    assert!(text.contains("1. f5 {Avoids {the\\} C-square \\\\ wall}"), "{text}");
+
+    let parsed: GameRecord = parse_record(&text).expect("valid record");
+    assert_eq!(Some(comment), parsed.moves[0].comment.as_deref());
+}
+
+#[test]
+fn test_result() {
+    let record: GameRecord = random_record();
+    let (black, white) = record.position().scores();
+    assert_eq!(Some(format!("{black}-{white}").as_str()), record.header(SCORE));
+    let expected = if black > white { "1-0" } else if black < white { "0-1" } else { "1/2-1/2" };
+    assert_eq!(Some(expected), record.header(RESULT));
+    assert!(write_record(&record).trim_end().ends_with(expected));
+}
+
+#[test]
+fn test_many_records() {
+    let records: Vec<GameRecord> = (0..5).map(|_| random_record()).collect();
+    let parsed: Vec<GameRecord> = parse_records(&write_records(&records)).expect("valid records");
+    assert_eq!(records.len(), parsed.len());
+    for (record, parsed) in records.iter().zip(&parsed) {
+        assert_eq!(record.moves, parsed.moves);
+    }
+}
+
+#[test]
+fn test_start_position() {
+    let start = "---------------------------OX------XXX-------------------------- O";
+    let mut record: GameRecord = GameRecord::from_position(parse_position(start).unwrap());
+    record.push(Move::parse("f6", Colour::White).unwrap());
+    let text = write_record(&record);
+    assert!(text.contains(&format!("[Position \"{start}\"]")));
+    assert!(text.contains("1... f6 *"));
+
+    let parsed: GameRecord = parse_record(&text).expect("valid record");
+    assert!(parsed.start == record.start);
+    assert_eq!(record.moves, parsed.moves);
+}
+
+#[test]
+fn test_errors() {
+    let err = parse_record::<BitBoardBoard>("[Black AlphaBetaAI]\n\n1. f5").unwrap_err();
+    assert_eq!(1, err.line_number);
+    assert!(matches!(err.kind, RecordErrorKind::InvalidHeader(_)));
+
+    let err = parse_record::<BitBoardBoard>("[Black \"X\"]\n\n1. f5\nd6 {unfinished").unwrap_err();
+    assert_eq!(4, err.line_number);
+    assert_eq!(RecordErrorKind::UnterminatedComment, err.kind);
+
+    let err = parse_record::<BitBoardBoard>("1. f5 {[%eval high]}").unwrap_err();
+    assert_eq!(RecordErrorKind::InvalidAnnotation("eval high".to_string()), err.kind);
+
+    let err = parse_record::<BitBoardBoard>("1. f5 d6\n2. a1").unwrap_err();
+    assert_eq!(2, err.line_number);
+    assert!(err.to_string().starts_with("line 2: "));
+    match err.kind {
+        RecordErrorKind::Move(err) => {
+            assert_eq!(3, err.move_number);
+            assert_eq!(TranscriptErrorKind::IllegalMove, err.kind);
+        }
+        kind => panic!("unexpected error {kind:?}"),
+    }
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "serde")]
+
+use othello_game::bitboardgame::BitBoardBoard;
+use othello_game::default::DefaultBoard;
+use othello_game::notation::parse_transcript;
+use othello_game::tableboard::TableBoard;
+use othello_game::{convert_board, random_board, Colour, GameRepr, Move};
+
+#[test]
+fn test_colour() {
+    assert_eq!(r#""black""#, serde_json::to_string(&Colour::Black).unwrap());
+    assert_eq!(Colour::White, serde_json::from_str::<Colour>(r#""white""#).unwrap());
+}
+
+#[test]
+fn test_move() {
+    let (_, moves): (GameRepr, _) = parse_transcript("f5d6").unwrap();
+    let json = r#"[{"player":"black","move":"f5"},{"player":"white","move":"d6"}]"#;
+    assert_eq!(json, serde_json::to_string(&moves).unwrap());
+    assert_eq!(moves, serde_json::from_str::<Vec<Move>>(json).unwrap());
+
+    /* The case of the move doesn't say whose it is */
+    let d3 = Move { player: Colour::Black, row: 2, col: 3 };
+    assert_eq!(d3, serde_json::from_str::<Move>(&serde_json::to_string(&d3).unwrap()).unwrap());
+    assert_eq!(d3, serde_json::from_str::<Move>(r#"{"player":"black","move":"d3"}"#).unwrap());
+    assert_eq!(Move { player: Colour::White, ..d3 }, serde_json::from_str::<Move>(r#"{"player":"white","move":"D3"}"#).unwrap());
+
+    let passes = vec![Move::pass(Colour::Black), Move::pass(Colour::White)];
+    let json = r#"[{"player":"black","move":"pa"},{"player":"white","move":"pa"}]"#;
+    assert_eq!(json, serde_json::to_string(&passes).unwrap());
+    assert_eq!(passes, serde_json::from_str::<Vec<Move>>(json).unwrap());
+
+    let err = serde_json::from_str::<Move>(r#"{"player":"black","move":"z9"}"#).unwrap_err();
+    assert!(err.to_string().contains("invalid column 'z'"), "{err}");
+}
+
+#[test]
+fn test_boards() {
+    for _ in 0..10 {
+        let board: BitBoardBoard = random_board();
+        let json = serde_json::to_string(&board).unwrap();
+        assert_eq!(66, json.len());
+        assert!(serde_json::from_str::<BitBoardBoard>(&json).unwrap() == board);
+
+        let default_board: DefaultBoard = convert_board(&board);
+        assert_eq!(json, serde_json::to_string(&default_board).unwrap());
+        assert!(serde_json::from_str::<DefaultBoard>(&json).unwrap() == default_board);
+
+        let table_board: TableBoard = serde_json::from_str(&json).unwrap();
+        assert!(convert_board::<_, BitBoardBoard>(&table_board) == board);
+    }
+
+    let board: DefaultBoard<4> = serde_json::from_str(r#""-----OX--XO-----""#).unwrap();
+    assert!(board == othello_game::Board::new());
+
+    let err = serde_json::from_str::<BitBoardBoard<6>>(r#""-----OX--XO-----""#).unwrap_err();
+    assert!(err.to_string().contains("expected 36 squares, found 16"), "{err}");
+}
+
+#[test]
+fn test_game() {
+    let (game, _): (GameRepr, _) = parse_transcript("f5").unwrap();
+    let json = serde_json::to_string(&game).unwrap();
+    assert_eq!(r#""---------------------------OX------XXX-------------------------- O""#, json);
+    assert!(serde_json::from_str::<GameRepr>(&json).unwrap() == game);
+    assert!(serde_json::from_str::<GameRepr<BitBoardBoard>>(&json).unwrap() == parse_transcript("f5").unwrap().0);
+}
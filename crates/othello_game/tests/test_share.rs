@@ -0,0 +1,92 @@
+use rand::seq::SliceRandom;
+
+use othello_game::bitboardgame::BitBoardBoard;
+use othello_game::default::DefaultBoard;
+use othello_game::notation::{parse_transcript, TranscriptErrorKind};
+use othello_game::share::{decode_share_code, encode_game, encode_position, ShareError, SharedGame};
+use othello_game::{Board, Colour, Game, GameRepr, Move};
+
+fn random_game<B: Board>() -> Vec<Move> {
+    let mut game: GameRepr<B> = GameRepr::new();
+    let mut moves = Vec::new();
+    while !game.is_game_over() {
+        let mov = game.valid_moves(game.next_turn)
+            .choose(&mut rand::thread_rng()).copied()
+            .unwrap_or(Move::pass(game.next_turn));
+        game = game.apply(mov);
+        moves.push(mov);
+    }
+    moves
+}
+
+fn is_url_safe(code: &str) -> bool {
+    code.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' || ch == '.')
+}
+
+#[test]
+fn test_position_code() {
+    let game: GameRepr = GameRepr::new();
+    let code = encode_position(&game);
+    assert!(code.starts_with('p'));
+    assert_eq!(25, code.len());
+    assert!(is_url_safe(&code));
+
+    let shared: SharedGame = decode_share_code(&code).expect("valid code");
+    assert!(shared.start == game);
+    assert!(shared.moves.is_empty());
+
+    let (game, _): (GameRepr, _) = parse_transcript("f5d6c3d3c4").unwrap();
+    let shared: SharedGame = decode_share_code(&encode_position(&game)).unwrap();
+    assert!(shared.position() == game);
+    assert_eq!(Colour::White, shared.position().next_turn);
+}
+
+#[test]
+fn test_game_code() {
+    for _ in 0..20 {
+        let moves = random_game::<BitBoardBoard>();
+        let code = encode_game(&GameRepr::<BitBoardBoard>::new(), &moves);
+        assert!(is_url_safe(&code));
+        assert_eq!(1 + moves.iter().filter(|mov| !mov.is_pass()).count(), code.len());
+
+        let shared: SharedGame<BitBoardBoard> = decode_share_code(&code).expect("valid code");
+        assert_eq!(moves, shared.moves);
+    }
+}
+
+#[test]
+fn test_other_sizes() {
+    let moves = random_game::<DefaultBoard<10>>();
+    let code = encode_game(&GameRepr::<DefaultBoard<10>>::new(), &moves);
+    let shared: SharedGame<DefaultBoard<10>> = decode_share_code(&code).unwrap();
+    assert_eq!(moves, shared.moves);
+
+    let moves = random_game::<DefaultBoard<6>>();
+    let start = GameRepr::<DefaultBoard<6>>::new().apply(moves[0]);
+    let code = encode_game(&start, &moves[1..]);
+    assert!(code.contains('.'));
+    let shared: SharedGame<DefaultBoard<6>> = decode_share_code(&code).unwrap();
+    assert_eq!(moves[1..], shared.moves);
+    assert_eq!(Colour::White, shared.start.next_turn);
+
+    let code = encode_position(&GameRepr::<DefaultBoard<6>>::new());
+    assert_eq!(Some(ShareError::WrongBoardSize { expected: 8, found: 6 }), decode_share_code::<DefaultBoard>(&code).err());
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(Some(ShareError::Empty), decode_share_code::<DefaultBoard>(" ").err());
+    assert_eq!(Some(ShareError::UnknownKind('q')), decode_share_code::<DefaultBoard>("qAAA").err());
+    assert_eq!(Some(ShareError::InvalidCharacter { index: 3, ch: '!' }), decode_share_code::<DefaultBoard>("pAA!").err());
+    assert_eq!(Some(ShareError::InvalidPosition), decode_share_code::<DefaultBoard>("pCA").err());
+
+    /* "l" is F5, then "A" is A1, which isn't legal */
+    match decode_share_code::<DefaultBoard>("glA") {
+        Err(ShareError::Move(err)) => {
+            assert_eq!(2, err.move_number);
+            assert_eq!("A", err.text);
+            assert_eq!(TranscriptErrorKind::IllegalMove, err.kind);
+        }
+        other => panic!("unexpected result {other:?}"),
+    }
+}
@@ -0,0 +1,159 @@
+use rand::seq::SliceRandom;
+
+use othello_game::bitboardgame::BitBoardBoard;
+use othello_game::default::DefaultBoard;
+use othello_game::notation::{parse_transcript, write_transcript};
+use othello_game::perft::perft;
+use othello_game::symmetry::Transform;
+use othello_game::tableboard::TableBoard;
+use othello_game::{convert_board, random_board, Board, Colour, Game, GameParseErrorKind, GameRepr, Move};
+
+#[test]
+fn test_new_small() {
+    let game: GameRepr<DefaultBoard<4>> = GameRepr::new();
+    assert_eq!("····\n·●○·\n·○●·\n····\n", format!("{game:?}"));
+
+    let game: GameRepr<BitBoardBoard<6>> = GameRepr::new();
+    assert_eq!("······\n······\n··●○··\n··○●··\n······\n······\n", format!("{game:?}"));
+    assert_eq!(vec!["C2", "B3", "E4", "D5"],
+               game.valid_moves(Colour::Black).iter().map(Move::to_string).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_new_large() {
+    let game: GameRepr<DefaultBoard<10>> = GameRepr::new();
+    assert_eq!(Some(Colour::White), game.get_piece(4, 4));
+    assert_eq!(Some(Colour::Black), game.get_piece(5, 4));
+    assert_eq!((2, 2), game.scores());
+    assert_eq!(10, format!("{game:?}").lines().count());
+}
+
+#[test]
+fn test_parse_sizes() {
+    let game: Result<GameRepr<DefaultBoard<4>>, _> = "····\n·●○·\n·○●·\n····\n·●○·".try_into();
+    assert_eq!(Some(GameParseErrorKind::TooManyRows), game.err().map(|err| err.kind));
+
+    let game: Result<GameRepr<TableBoard<6>>, _> = "·······".try_into();
+    assert_eq!(Some(GameParseErrorKind::TooManyColumns), game.err().map(|err| err.kind));
+
+    let game: GameRepr<DefaultBoard<10>> = "·········○".try_into().expect("ok");
+    assert_eq!(Some(Colour::Black), game.get_piece(0, 9));
+}
+
+#[test]
+fn test_perft_small() {
+    const PERFT_4X4: &[u64] = &[4, 12, 44, 128, 424, 1256, 3624, 9116, 20044, 36540];
+    const PERFT_6X6: &[u64] = &[4, 12, 56, 244, 1364, 7604, 47740];
+
+    let game: GameRepr<BitBoardBoard<4>> = GameRepr::new();
+    let default_game: GameRepr<DefaultBoard<4>> = GameRepr::new();
+    for (depth, expected) in PERFT_4X4.iter().enumerate() {
+        assert_eq!(*expected, perft(&game, depth + 1), "depth {}", depth + 1);
+        assert_eq!(*expected, perft(&default_game, depth + 1), "depth {}", depth + 1);
+    }
+
+    let game: GameRepr<TableBoard<6>> = GameRepr::new();
+    let default_game: GameRepr<DefaultBoard<6>> = GameRepr::new();
+    for (depth, expected) in PERFT_6X6.iter().enumerate() {
+        assert_eq!(*expected, perft(&game, depth + 1), "depth {}", depth + 1);
+        assert_eq!(*expected, perft(&default_game, depth + 1), "depth {}", depth + 1);
+    }
+}
+
+/**
+ * The score for the player to move with perfect play by both sides.
+ */
+fn solve<B: Board>(game: &GameRepr<B>) -> i32 {
+    if game.is_game_over() {
+        let (black, white) = game.scores();
+        return (black - white) * game.next_turn.sign();
+    }
+    let moves = game.valid_moves(game.next_turn);
+    if moves.is_empty() {
+        return -solve(&game.pass());
+    }
+    moves.into_iter().map(|mov| -solve(&game.apply(mov))).max().unwrap()
+}
+
+#[test]
+fn test_solve_4x4() {
+    /* 4x4 Othello is a win for White, by 11 to 3 */
+    let game: GameRepr<BitBoardBoard<4>> = GameRepr::new();
+    assert_eq!(-8, solve(&game));
+}
+
+fn random_game<B: Board>() -> (GameRepr<B>, Vec<Move>) {
+    let mut game: GameRepr<B> = GameRepr::new();
+    let mut moves = Vec::new();
+    while !game.is_game_over() {
+        let mov = *game.valid_moves(game.next_turn)
+            .choose(&mut rand::thread_rng())
+            .unwrap_or(&Move::pass(game.next_turn));
+        game = game.apply(mov);
+        moves.push(mov);
+    }
+    (game, moves)
+}
+
+#[test]
+fn test_random_games_6x6() {
+    for _ in 0..100 {
+        let (_, moves) = random_game::<DefaultBoard<6>>();
+        let mut default_game: GameRepr<DefaultBoard<6>> = GameRepr::new();
+        let mut bitboard_game: GameRepr<BitBoardBoard<6>> = GameRepr::new();
+        let mut table_game: GameRepr<TableBoard<6>> = GameRepr::new();
+
+        for mov in moves {
+            assert!(bitboard_game.is_valid_move(mov) && table_game.is_valid_move(mov));
+            assert_eq!(default_game.valid_moves(default_game.next_turn), bitboard_game.valid_moves(bitboard_game.next_turn));
+            default_game = default_game.apply(mov);
+            bitboard_game = bitboard_game.apply(mov);
+            table_game = table_game.apply(mov);
+
+            assert!(convert_board::<_, BitBoardBoard<6>>(&default_game.board) == bitboard_game.board);
+            assert!(convert_board::<_, BitBoardBoard<6>>(&table_game.board) == bitboard_game.board);
+            assert_eq!(default_game.zobrist_key(), bitboard_game.zobrist_key());
+        }
+        assert!(bitboard_game.is_game_over() && table_game.is_game_over());
+    }
+}
+
+#[test]
+fn test_transcript_10x10() {
+    for _ in 0..10 {
+        let (game, moves) = random_game::<DefaultBoard<10>>();
+        let transcript = write_transcript(&moves);
+        let (game2, moves2): (GameRepr<DefaultBoard<10>>, _) = parse_transcript(&transcript).expect("legal transcript");
+        assert_eq!(moves, moves2);
+        assert!(game == game2);
+    }
+
+    assert_eq!("J10", Move { player: Colour::Black, row: 9, col: 9 }.to_string());
+    assert_eq!("a10j9", write_transcript(&[
+        Move { player: Colour::Black, row: 9, col: 0 },
+        Move { player: Colour::White, row: 8, col: 9 },
+    ]));
+}
+
+#[test]
+fn test_transforms_6x6() {
+    for _ in 0..100 {
+        let bitboard: BitBoardBoard<6> = random_board();
+        let default_board: DefaultBoard<6> = convert_board(&bitboard);
+
+        for t in Transform::ALL {
+            let bb2 = bitboard.transform(t);
+            assert!(convert_board::<_, BitBoardBoard<6>>(&default_board.transform(t)) == bb2, "{t:?}");
+            assert_eq!(default_board.transform(t).zobrist_key(), bb2.zobrist_key());
+            for (row, col) in [(0, 0), (0, 5), (2, 3), (5, 1)] {
+                let (row2, col2) = t.apply(row, col, 6);
+                assert_eq!(bitboard.get(row, col), bb2.get(row2, col2), "{t:?}");
+            }
+        }
+
+        let (canonical, transform) = bitboard.canonical();
+        let (default_canonical, default_transform) = default_board.canonical();
+        assert!(convert_board::<_, BitBoardBoard<6>>(&default_canonical) == canonical);
+        assert_eq!(transform, default_transform);
+    }
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "svg")]
+
+use std::fs;
+use std::path::Path;
+
+use othello_game::bitboardgame::BitBoardBoard;
+use othello_game::default::DefaultBoard;
+use othello_game::notation::parse_transcript;
+use othello_game::svg::{render_game_svg, render_svg, Annotation, SvgOptions};
+use othello_game::{Colour, GameRepr, Move};
+
+/**
+ * Compare against a saved diagram.  Run with `UPDATE_SNAPSHOTS=1` to save new diagrams.
+ */
+fn assert_snapshot(name: &str, svg: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots").join(name);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, svg).unwrap();
+    }
+    let expected = fs::read_to_string(&path).expect("snapshot exists");
+    assert_eq!(expected, svg, "diagram differs from {}", path.display());
+}
+
+fn count(svg: &str, pattern: &str) -> usize {
+    svg.matches(pattern).count()
+}
+
+#[test]
+fn test_start_position() {
+    let game: GameRepr = GameRepr::new();
+    let svg = render_svg(&game, &SvgOptions::default());
+    assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="360" height="360""#));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(4, count(&svg, r##"fill="#000000" stroke"##) + count(&svg, r##"fill="#ffffff" stroke"##));
+    assert_eq!(16, count(&svg, "</text>"));
+    assert_snapshot("start.svg", &svg);
+}
+
+#[test]
+fn test_options() {
+    let game: GameRepr<BitBoardBoard> = GameRepr::new();
+    let options = SvgOptions { square_size: 20, coordinates: false, legal_moves: true, ..Default::default() };
+    let svg = render_svg(&game, &options);
+    assert!(svg.contains(r#"width="160" height="160""#));
+    assert_eq!(0, count(&svg, "</text>"));
+    assert_eq!(4, count(&svg, r#"fill-opacity="0.4""#));
+
+    let options = SvgOptions {
+        last_move: Some(Move::parse("d3", Colour::Black).unwrap()),
+        annotations: vec![
+            Annotation::Arrow { from: (2, 3), to: (5, 4), colour: "red".to_string() },
+            Annotation::Label { square: (0, 0), text: "a<b".to_string(), colour: "blue".to_string() },
+        ],
+        ..Default::default()
+    };
+    let svg = render_svg(&game, &options);
+    assert_eq!(1, count(&svg, "#fdd835"));
+    assert_eq!(1, count(&svg, r#"marker-end="url(#arrowhead)""#));
+    assert_eq!(1, count(&svg, r#"<marker id="arrowhead""#));
+    assert!(svg.contains(r#"fill="blue">a&lt;b</text>"#));
+}
+
+#[test]
+fn test_game() {
+    let start: GameRepr = GameRepr::new();
+    let (_, moves): (GameRepr, _) = parse_transcript("f5d6c3d3c4f4f6f3e6e7").unwrap();
+    let options = SvgOptions { coordinates: false, ..Default::default() };
+    let svg = render_game_svg(&start, &moves, &options);
+    assert_eq!(10, count(&svg, "</text>"));
+    assert_eq!(1, count(&svg, "#fdd835"));
+    assert_snapshot("rose.svg", &svg);
+}
+
+#[test]
+fn test_small_board() {
+    let game: GameRepr<DefaultBoard<6>> = GameRepr::new();
+    let svg = render_svg(&game, &SvgOptions::default());
+    assert!(svg.contains(r#"width="280" height="280""#));
+    assert_eq!(12, count(&svg, "</text>"));
+    assert_eq!(14, count(&svg, "<line"));
+}
@@ -0,0 +1,60 @@
+use othello_game::bitboardgame::BitBoardBoard;
+use othello_game::default::DefaultBoard;
+use othello_game::symmetry::Transform;
+use othello_game::{convert_board, random_board, Board, Colour, Game, GameRepr};
+
+#[test]
+fn test_bitboard_transforms_match_default() {
+    for _ in 0..100 {
+        let bitboard: BitBoardBoard = random_board();
+        let default_board: DefaultBoard = convert_board(&bitboard);
+
+        for t in Transform::ALL {
+            let bb2 = bitboard.transform(t);
+            let default2: DefaultBoard = default_board.transform(t);
+            assert!(convert_board::<_, BitBoardBoard>(&default2) == bb2, "{t:?}");
+            assert_eq!(default2.zobrist_key(), bb2.zobrist_key());
+            assert!(bb2.transform(t.inverse()) == bitboard);
+        }
+    }
+}
+
+#[test]
+fn test_canonical() {
+    for _ in 0..100 {
+        let bitboard: BitBoardBoard = random_board();
+        let default_board: DefaultBoard = convert_board(&bitboard);
+        let (canonical, transform) = bitboard.canonical();
+        assert!(bitboard.transform(transform) == canonical);
+
+        let (default_canonical, default_transform) = default_board.canonical();
+        assert!(convert_board::<_, BitBoardBoard>(&default_canonical) == canonical);
+        assert_eq!(transform, default_transform);
+
+        for t in Transform::ALL {
+            assert!(bitboard.transform(t).canonical().0 == canonical);
+        }
+    }
+}
+
+#[test]
+fn test_canonical_openings() {
+    let game: GameRepr<BitBoardBoard> = GameRepr::new();
+    let openings: Vec<_> = game.valid_moves(Colour::Black).into_iter()
+        .map(|mov| game.apply(mov))
+        .collect();
+
+    /* All four first moves are equivalent */
+    let (canonical, _) = openings[0].canonical();
+    for opening in &openings {
+        assert!(opening.canonical().0 == canonical);
+    }
+
+    /* Map a move on the canonical board back to the original */
+    let (canonical, transform) = openings[2].canonical();
+    for mov in canonical.valid_moves(Colour::White) {
+        let original_mov = transform.inverse().apply_move(mov, <BitBoardBoard>::SIZE);
+        assert!(openings[2].is_valid_move(original_mov));
+        assert!(openings[2].apply(original_mov).transform(transform) == canonical.apply(mov));
+    }
+}
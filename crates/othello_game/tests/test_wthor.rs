@@ -0,0 +1,119 @@
+use rand::seq::SliceRandom;
+
+use othello_game::bitboardgame::BitBoardBoard;
+use othello_game::notation::parse_transcript;
+use othello_game::wthor::{read_players, read_tournaments, write_games, write_names, WthorError, WthorGame, WthorGames,
+                          WthorHeader, GAME_RECORD_LEN, HEADER_LEN, PLAYER_RECORD_LEN, TOURNAMENT_RECORD_LEN};
+use othello_game::{Game, GameRepr, Move};
+
+fn header() -> WthorHeader {
+    WthorHeader {
+        created_year: 2024,
+        created_month: 3,
+        created_day: 17,
+        game_year: 2024,
+        depth: 22,
+        ..Default::default()
+    }
+}
+
+fn random_game() -> Vec<Move> {
+    let mut game: GameRepr<BitBoardBoard> = GameRepr::new();
+    let mut moves = Vec::new();
+    while !game.is_game_over() {
+        let mov = game.valid_moves(game.next_turn)
+            .choose(&mut rand::thread_rng()).copied()
+            .unwrap_or(Move::pass(game.next_turn));
+        game = game.apply(mov);
+        moves.push(mov);
+    }
+    moves
+}
+
+#[test]
+fn test_header() {
+    let mut bytes = Vec::new();
+    header().write(&mut bytes).unwrap();
+    assert_eq!(HEADER_LEN, bytes.len());
+    assert_eq!([20, 24, 3, 17], bytes[..4]);
+    assert_eq!(header(), WthorHeader::read(&mut &bytes[..]).unwrap());
+
+    /* Older files give the board size as 0 */
+    bytes[12] = 0;
+    assert_eq!(8, WthorHeader::read(&mut &bytes[..]).unwrap().board_size);
+}
+
+#[test]
+fn test_games() {
+    let (_, opening) = parse_transcript::<BitBoardBoard>("f5d6c3d3c4").unwrap();
+    let games: Vec<WthorGame> = (0..20)
+        .map(|i| WthorGame::from_moves(i % 3, i, i + 1, if i == 0 { opening.clone() } else { random_game() }))
+        .collect();
+
+    let mut bytes = Vec::new();
+    write_games(&mut bytes, &header(), &games).unwrap();
+    assert_eq!(HEADER_LEN + 20 * GAME_RECORD_LEN, bytes.len());
+
+    let reader = WthorGames::new(&bytes[..]).unwrap();
+    assert_eq!(20, reader.header().num_games);
+    let read: Vec<WthorGame> = reader.collect::<Result<_, _>>().unwrap();
+    assert_eq!(games, read);
+
+    /* The score counts empty squares for the winner */
+    for game in &read {
+        let (black, white) = game.position::<BitBoardBoard>().scores();
+        assert!(black as u8 <= game.score && game.score <= (64 - white) as u8);
+        assert_eq!(game.score, game.theoretical_score);
+    }
+    assert_eq!(opening, read[0].moves);
+}
+
+#[test]
+fn test_names() {
+    let players = vec!["Tastet Marc".to_string(), "Müller".to_string(), "A very long name that is cut short".to_string()];
+    let mut bytes = Vec::new();
+    write_names(&mut bytes, &header(), &players, PLAYER_RECORD_LEN).unwrap();
+    assert_eq!(HEADER_LEN + 3 * PLAYER_RECORD_LEN, bytes.len());
+
+    let read = read_players(&mut &bytes[..]).unwrap();
+    assert_eq!(vec!["Tastet Marc", "Müller", "A very long name th"], read);
+
+    let tournaments = vec!["Championnat de France".to_string()];
+    let mut bytes = Vec::new();
+    write_names(&mut bytes, &header(), &tournaments, TOURNAMENT_RECORD_LEN).unwrap();
+    let read_tournaments = read_tournaments(&mut &bytes[..]).unwrap();
+
+    let game = WthorGame::from_moves(0, 1, 0, vec![]);
+    assert_eq!(Some("Müller"), game.black_name(&read));
+    assert_eq!(Some("Tastet Marc"), game.white_name(&read));
+    assert_eq!(Some("Championnat de France"), game.tournament_name(&read_tournaments));
+    assert_eq!(None, WthorGame::from_moves(1, 5, 0, vec![]).tournament_name(&read_tournaments));
+}
+
+#[test]
+fn test_errors() {
+    let games = vec![WthorGame::from_moves(0, 0, 0, random_game()), WthorGame::from_moves(0, 0, 0, random_game())];
+    let mut bytes = Vec::new();
+    write_games(&mut bytes, &header(), &games).unwrap();
+
+    /* Truncated file */
+    let results: Vec<_> = WthorGames::new(&bytes[..bytes.len() - 1]).unwrap().collect();
+    assert_eq!(2, results.len());
+    assert!(matches!(results[1], Err(WthorError::Io(_))));
+
+    /* Illegal first move, A1 */
+    let mut bad = bytes.clone();
+    bad[HEADER_LEN + 8] = 11;
+    let err = WthorGames::new(&bad[..]).unwrap().next().unwrap().unwrap_err();
+    assert_eq!("game 1: move 1 \"A1\" is illegal", err.to_string());
+
+    /* Not a square */
+    let mut bad = bytes.clone();
+    bad[HEADER_LEN + GAME_RECORD_LEN + 9] = 19;
+    assert!(matches!(WthorGames::new(&bad[..]).unwrap().nth(1),
+        Some(Err(WthorError::InvalidMove { game_number: 2, move_number: 2, value: 19 }))));
+
+    let mut bad = bytes.clone();
+    bad[12] = 10;
+    assert!(matches!(WthorGames::new(&bad[..]), Err(WthorError::UnsupportedBoardSize(10))));
+}
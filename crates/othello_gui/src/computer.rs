@@ -4,7 +4,7 @@ use bevy::prelude::{Component, EventReader, EventWriter, Query, ResMut, Single,
 use bevy::tasks::{block_on, AsyncComputeTaskPool, Task};
 use bevy::tasks::futures_lite::future;
 
-use othello_ai::{MinimaxAI, RandomAI, AI};
+use othello_ai::{AIInfo, AlphaBetaAI, MinimaxAI, RandomAI, AI};
 use othello_game::{convert, DefaultGame, Game, Move};
 
 use crate::game::{CurrentGame, GameEvent, Player};
@@ -29,7 +29,8 @@ pub struct Computer {
 #[derive(Clone)]
 pub enum AIType {
     RandomAI(RandomAI),
-    MinimaxAI(MinimaxAI)
+    MinimaxAI(MinimaxAI),
+    AlphaBetaAI(AlphaBetaAI),
 }
 
 impl AIType {
@@ -37,6 +38,15 @@ impl AIType {
         match self {
             AIType::RandomAI(ai) => ai.choose_move(game),
             AIType::MinimaxAI(ai) => ai.choose_move(game),
+            AIType::AlphaBetaAI(ai) => ai.choose_move(game),
+        }
+    }
+
+    fn info(&self) -> Option<AIInfo> {
+        match self {
+            AIType::RandomAI(ai) => ai.info(),
+            AIType::MinimaxAI(ai) => ai.info(),
+            AIType::AlphaBetaAI(ai) => ai.info(),
         }
     }
 }
@@ -88,9 +98,7 @@ fn update_ai_info(
     if game_events.is_empty() { return }
 
     for computer in computers.iter() {
-        let AIType::MinimaxAI(ai) = &computer.ai
-        else { continue };
-        let Some(info) = ai.info()
+        let Some(info) = computer.ai.info()
         else { continue };
         ai_text.0 = format!(
             "AI Info:\n\
@@ -101,5 +109,17 @@ fn update_ai_info(
             info.last_nodes_searched.get(),
             info.last_num_choices.get(),
         );
+        if info.last_depth.get() > 0 {
+            ai_text.0.push_str(&format!("Last Depth: {}\n", info.last_depth.get()));
+        }
+        if info.table_probes.get() > 0 {
+            ai_text.0.push_str(&format!(
+                "Table Hits: {}/{}\n\
+                Table Cutoffs: {}\n",
+                info.table_hits.get(),
+                info.table_probes.get(),
+                info.table_cutoffs.get(),
+            ));
+        }
     }
 }
@@ -6,11 +6,12 @@ use bevy::log::{error, info};
 use bevy::prelude::{Commands, Component, Entity, Event, EventReader, Query, Real, Res, ResMut, Resource, Text2d, Time, With, Without};
 use bevy::time::Stopwatch;
 
-use othello_ai::MinimaxAI;
+use othello_ai::{AlphaBetaAI, DeepeningAI};
 use othello_game::{Colour, DefaultGame, Game, Move, Pos};
 
 use crate::computer::{AIType, Computer};
 use crate::rendering::{ScoreLabel, TimeLabel};
+use crate::share::shared_game;
 
 pub struct GamePlugin;
 
@@ -35,7 +36,7 @@ pub struct CurrentGame {
 impl Default for CurrentGame {
     fn default() -> Self {
         CurrentGame {
-            game: Box::new(DefaultGame::new()),
+            game: Box::new(shared_game().unwrap_or_default()),
             over: false,
         }
     }
@@ -74,6 +75,19 @@ pub enum GameEvent {
     ClickSquare { row: Pos, col: Pos },
 }
 
+/**
+ * The computer player thinks for about a second a move.  There's no clock to check in the browser, so
+ * there it's given a number of nodes instead.
+ */
+fn computer_ai() -> AlphaBetaAI {
+    let ai = AlphaBetaAI::new(60);
+    #[cfg(not(target_arch = "wasm32"))]
+    let ai = ai.with_time_limit(std::time::Duration::from_secs(1));
+    #[cfg(target_arch = "wasm32")]
+    let ai = ai.with_node_limit(1_000_000);
+    ai
+}
+
 pub fn setup_players(
     mut commands: Commands,
 ) {
@@ -87,7 +101,7 @@ pub fn setup_players(
             player_time: Stopwatch::new(),
         },
         Computer {
-            ai: AIType::MinimaxAI(MinimaxAI::new(6)),
+            ai: AIType::AlphaBetaAI(computer_ai()),
             task: None,
         }
     ));
@@ -156,16 +170,23 @@ fn handle_game_events(
         }
     }
 
-    /* Check if other player now can't go */
+    /* Check if the game has ended, or if the other player now can't go and must pass */
     if !current_game.over {
-        for player in players.iter() {
-            if player.colour != current_game.game.next_turn() {
-                continue;
+        if current_game.game.is_game_over() {
+            let (black_score, white_score) = current_game.game.scores();
+            for player in players.iter() {
+                player.sender.send(format!("Game over: {black_score}-{white_score}")).unwrap();
             }
+            current_game.over = true;
+        } else if current_game.game.must_pass() {
+            for player in players.iter() {
+                if player.colour != current_game.game.next_turn() {
+                    continue;
+                }
 
-            if current_game.game.valid_moves(player.colour).is_empty() {
                 player.sender.send(format!("{} can't go", player.name)).unwrap();
-                current_game.over = true;
+                current_game.game.apply_in_place(Move::pass(player.colour));
+                break;
             }
         }
     }
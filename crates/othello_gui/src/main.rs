@@ -14,6 +14,7 @@ mod computer;
 mod game;
 mod utils;
 mod rendering;
+mod share;
 
 fn main() {
     App::new()
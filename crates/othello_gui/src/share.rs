@@ -0,0 +1,37 @@
+use bevy::log::{error, info};
+
+use othello_game::DefaultGame;
+use othello_game::share::decode_share_code;
+
+/**
+ * The share code the game was opened with: the fragment of the page's URL in a browser, as in
+ * "othello.html#gl", or the first argument on the command line.
+ */
+#[cfg(target_arch = "wasm32")]
+fn share_code() -> Option<String> {
+    let hash = web_sys::window()?.location().hash().ok()?;
+    let code = hash.trim_start_matches('#');
+    (!code.is_empty()).then(|| code.to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn share_code() -> Option<String> {
+    std::env::args().nth(1)
+}
+
+/**
+ * The position to start from, if a valid share code was given.
+ */
+pub fn shared_game() -> Option<DefaultGame> {
+    let code = share_code()?;
+    match decode_share_code(&code) {
+        Ok(shared) => {
+            info!("Opening shared game {code}");
+            Some(shared.position())
+        }
+        Err(err) => {
+            error!("Can't open shared game \"{code}\": {err}");
+            None
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use rand::seq::SliceRandom;
 
-use othello_game::{DefaultGame, Game};
+use othello_game::{DefaultGame, Game, Move};
 
 fn run_one_game() -> (usize, usize, usize, Option<DefaultGame>) {
     let mut turns: usize = 0;
@@ -10,8 +10,13 @@ fn run_one_game() -> (usize, usize, usize, Option<DefaultGame>) {
     let mut game = DefaultGame::new();
 
     loop {
+        if game.is_game_over() { break }
+
         let moves: Vec<_> = game.valid_moves(game.next_turn).into_iter().collect();
-        if moves.is_empty() { break }
+        if moves.is_empty() {
+            game = game.apply(Move::pass(game.next_turn));
+            continue;
+        }
 
         let num = moves.len();
         turns += 1;
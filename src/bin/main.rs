@@ -1,44 +1,96 @@
 use std::cmp::min;
-use std::sync::atomic::{AtomicIsize, AtomicUsize, Ordering};
+use std::fs;
+use std::sync::atomic::{AtomicIsize, Ordering};
+use std::sync::Mutex;
 use std::thread;
-use std::time::SystemTime;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use othello_ai::{AI, evaluate_immediate, AlphaBetaAI, RandomAI};
-use othello_game::{Colour, DefaultGame, GameRepr};
+use othello_game::{Colour, DefaultGame, Game, Move};
+use othello_game::record::{self, write_records, GameRecord, RecordedMove};
+
+/**
+ * The name of an AI's type, such as "AlphaBetaAI".
+ */
+fn ai_name<T>(_ai: &T) -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
 
-fn simulate_one_game(black_ai: impl AI, white_ai: impl AI) -> GameRepr {
-    let mut game = DefaultGame::new();
-    // println!("Game: {:?}", &othello_game);
+/**
+ * Today's date as "YYYY.MM.DD", in UTC.
+ */
+fn today() -> String {
+    let days = SystemTime::now().duration_since(UNIX_EPOCH).expect("no time travel").as_secs() as i64 / 86400;
+
+    /* Convert days since 1970-01-01 to a civil date; the years run from March to February */
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{year:04}.{month:02}.{day:02}")
+}
+
+/**
+ * The headers naming the players, with each AI's settings, such as "max_depth=4 ordering=Heuristic".
+ */
+fn player_headers(black_ai: &impl AI, black_config: &str, white_ai: &impl AI, white_config: &str) -> Vec<(&'static str, String)> {
+    vec![
+        (record::BLACK, ai_name(black_ai).to_string()),
+        (record::WHITE, ai_name(white_ai).to_string()),
+        (record::BLACK_AI, black_config.to_string()),
+        (record::WHITE_AI, white_config.to_string()),
+    ]
+}
 
-    loop {
+fn simulate_one_game(black_ai: impl AI, white_ai: impl AI, headers: &[(&'static str, String)]) -> GameRecord {
+    let mut record = GameRecord::new();
+    record.set_header(record::EVENT, "Self-play");
+    record.set_header(record::DATE, today());
+    for (name, value) in headers {
+        record.set_header(name, value.as_str());
+    }
+    record.set_header(record::TIME_CONTROL, "-");
+
+    let mut game = DefaultGame::new();
+    while !game.is_game_over() {
+        let t0 = Instant::now();
         let mov = if game.next_turn == Colour::Black { black_ai.choose_move(&game) }
             else { white_ai.choose_move(&game) };
+        /* Whole microseconds keep the record short */
+        let time = t0.elapsed().as_micros() as f64 / 1e6;
 
-        let Some(mov) = mov else {
-            // println!("No more moves!");
-            break;
-        };
+        /* An AI with no moves available has to pass */
+        let mov = mov.unwrap_or(Move::pass(game.next_turn));
 
-        // println!("Move: {:?}", mov);
         game = game.apply(mov);
+        record.push(RecordedMove { mov, eval: None, time: Some(time), comment: None });
     }
 
-    game
+    record.set_result();
+    record
 }
 
-fn simulate_many_games(black_ai: &impl AI, white_ai: &impl AI, num_games: usize) -> isize {
+fn simulate_many_games(black_ai: &impl AI, white_ai: &impl AI, headers: &[(&'static str, String)], num_games: usize) -> (isize, Vec<GameRecord>) {
     let mut total_score = 0;
+    let mut records = Vec::with_capacity(num_games);
     for _ in 0..num_games {
-        let game = simulate_one_game(black_ai.clone(), white_ai.clone());
-        let score = evaluate_immediate(&game, Colour::Black);
+        let record = simulate_one_game(black_ai.clone(), white_ai.clone(), headers);
+        let score = evaluate_immediate(&record.position(), Colour::Black);
         total_score += score as isize;
+        records.push(record);
     }
-    total_score
+    (total_score, records)
 }
 
-fn simulate_many_games_in_parallel(black_ai: &impl AI, white_ai: &impl AI, num_games: usize, num_threads: usize) -> (isize, usize) {
+fn simulate_many_games_in_parallel(black_ai: &impl AI, white_ai: &impl AI, headers: &[(&'static str, String)], num_games: usize, num_threads: usize) -> (isize, Vec<GameRecord>) {
     let total_score = AtomicIsize::new(0);
-    let games_run: AtomicUsize = AtomicUsize::new(0);
+    let records = Mutex::new(Vec::with_capacity(num_games));
 
     fn make_chunks(mut total: usize, num_chunks: usize) -> Vec<usize> {
         let mut chunks = Vec::new();
@@ -58,34 +110,48 @@ fn simulate_many_games_in_parallel(black_ai: &impl AI, white_ai: &impl AI, num_g
             let black_ai = black_ai.clone();
             let white_ai = white_ai.clone();
             let total_score = &total_score;
-            let games_run = &games_run;
+            let records = &records;
 
             s.spawn(move || {
-                let thread_score = simulate_many_games(&black_ai, &white_ai, games_per_thread);
+                let (thread_score, thread_records) = simulate_many_games(&black_ai, &white_ai, headers, games_per_thread);
                 total_score.fetch_add(thread_score, Ordering::Relaxed);
-                games_run.fetch_add(games_per_thread, Ordering::Relaxed);
+                records.lock().expect("no other thread panicked").extend(thread_records);
             });
         }
     });
     let total_score = total_score.into_inner();
-    let games_run = games_run.into_inner();
+    let records = records.into_inner().expect("no other thread panicked");
 
-    (total_score, games_run)
+    (total_score, records)
 }
 
+/**
+ * Usage: main [RECORD_FILE]
+ *
+ * If a file is given, a record of every game is written to it.
+ */
 fn main() {
     println!("Othello");
+    let record_path = std::env::args().nth(1);
 
-    let black_ai = AlphaBetaAI { max_depth: 3 };
+    let black_ai = AlphaBetaAI::new(4);
     let white_ai = RandomAI { };
+    let black_config = format!("max_depth={} ordering={:?}", black_ai.deepening.max_depth, black_ai.move_ordering);
+    let headers = player_headers(&black_ai, &black_config, &white_ai, "-");
 
     let num_games = 1000;
     let num_threads = thread::available_parallelism()
         .map_or(1, |x| x.get());
     let t0 = SystemTime::now();
-    let (total_score, games_run) = simulate_many_games_in_parallel(&black_ai, &white_ai, num_games, num_threads);
+    let (total_score, records) = simulate_many_games_in_parallel(&black_ai, &white_ai, &headers, num_games, num_threads);
+    let games_run = records.len();
     println!("Simulating {} games on {} threads took {:?}",
              games_run, num_threads, t0.elapsed().expect("no time travel"));
 
     println!("Average score={:2.2}", total_score as f64 / games_run as f64);
+
+    if let Some(path) = record_path {
+        fs::write(&path, write_records(&records)).expect("can write records");
+        println!("Wrote {games_run} game records to {path}");
+    }
 }
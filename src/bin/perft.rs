@@ -0,0 +1,79 @@
+use std::env;
+use std::thread;
+use std::time::SystemTime;
+
+use othello_game::bitboardgame::BitBoardBoard;
+use othello_game::tableboard::TableBoard;
+use othello_game::perft::{perft_divide, perft_parallel, STANDARD_PERFT};
+use othello_game::{Board, GameRepr};
+use othello_game::default::DefaultBoard;
+
+/**
+ * Count moves from the starting position to each depth, checking the counts against the
+ * published perft numbers.
+ *
+ * Usage: perft [max depth] [--divide] [--threads N] [--default | --table]
+ */
+fn main() {
+    let mut max_depth = 10;
+    let mut divide = false;
+    let mut use_default = false;
+    let mut use_table = false;
+    let mut num_threads = thread::available_parallelism()
+        .map_or(1, |x| x.get());
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--divide" => divide = true,
+            "--default" => use_default = true,
+            "--table" => use_table = true,
+            "--threads" => {
+                num_threads = args.next().and_then(|n| n.parse().ok()).expect("number of threads");
+            }
+            _ => max_depth = arg.parse().expect("maximum depth"),
+        }
+    }
+
+    let ok = if use_default {
+        run_perft::<DefaultBoard>(max_depth, divide, num_threads)
+    } else if use_table {
+        run_perft::<TableBoard>(max_depth, divide, num_threads)
+    } else {
+        run_perft::<BitBoardBoard>(max_depth, divide, num_threads)
+    };
+
+    if !ok {
+        std::process::exit(1);
+    }
+}
+
+fn run_perft<B: Board + Send + Sync>(max_depth: usize, divide: bool, num_threads: usize) -> bool {
+    let game: GameRepr<B> = GameRepr::new();
+    let mut ok = true;
+
+    if divide {
+        for (mov, count) in perft_divide(&game, max_depth) {
+            println!("{mov}: {count}");
+        }
+    }
+
+    for depth in 1..=max_depth {
+        let t0 = SystemTime::now();
+        let count = perft_parallel(&game, depth, num_threads);
+        let elapsed = t0.elapsed().expect("no time travel");
+
+        let status = match STANDARD_PERFT.get(depth - 1) {
+            Some(expected) if *expected == count => "ok".to_string(),
+            Some(expected) => {
+                ok = false;
+                format!("MISMATCH, expected {expected}")
+            }
+            None => "unknown".to_string(),
+        };
+        let rate = count as f64 / elapsed.as_secs_f64().max(1e-9) / 1e6;
+        println!("perft({depth}) = {count} in {elapsed:?} ({rate:.1} M leaves/s) {status}");
+    }
+
+    ok
+}
@@ -21,7 +21,13 @@ fn random_game() {
         num_turns += 1;
         total_moves += num_moves;
 
-        if moves.is_empty() { break }
+        if game.is_game_over() { break }
+
+        if moves.is_empty() {
+            println!("{player:?} has to pass");
+            game = game.apply(Move::pass(player));
+            continue;
+        }
 
         /* Make a random move */
         let mov = moves.choose(&mut rand::thread_rng()).expect("at least one move");